@@ -2916,13 +2916,21 @@ Discard the conflicting changes with `jj restore --from {}`.",
 
 /// Prints warning about explicit paths that don't match any of the tree
 /// entries.
+///
+/// Paths of `untracked_paths` and their parent directories are not reported.
 pub fn print_unmatched_explicit_paths<'a>(
     ui: &Ui,
     workspace_command: &WorkspaceCommandHelper,
     expression: &FilesetExpression,
     trees: impl IntoIterator<Item = &'a MergedTree>,
+    untracked_paths: &[&RepoPathBuf],
 ) -> io::Result<()> {
     let mut explicit_paths = expression.explicit_paths().collect_vec();
+    explicit_paths.retain(|&path| {
+        !untracked_paths
+            .iter()
+            .any(|untracked| untracked.starts_with(path))
+    });
     for tree in trees {
         // TODO: propagate errors
         explicit_paths.retain(|&path| tree.path_value(path).unwrap().is_absent());
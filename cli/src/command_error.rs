@@ -41,6 +41,8 @@ use jj_lib::op_heads_store::OpHeadsStoreError;
 use jj_lib::op_store::OpStoreError;
 use jj_lib::op_walk::OpsetEvaluationError;
 use jj_lib::op_walk::OpsetResolutionError;
+use jj_lib::patch::PatchApplyError;
+use jj_lib::patch::PatchParseError;
 use jj_lib::repo::CheckOutCommitError;
 use jj_lib::repo::EditCommitError;
 use jj_lib::repo::RepoLoaderError;
@@ -758,6 +760,21 @@ impl From<FixError> for CommandError {
     }
 }
 
+impl From<PatchParseError> for CommandError {
+    fn from(err: PatchParseError) -> Self {
+        user_error_with_message("Failed to parse patch", err)
+    }
+}
+
+impl From<PatchApplyError> for CommandError {
+    fn from(err: PatchApplyError) -> Self {
+        match err {
+            PatchApplyError::Backend(err) => err.into(),
+            _ => user_error_with_message("Failed to apply patch", err),
+        }
+    }
+}
+
 impl From<BisectionError> for CommandError {
     fn from(err: BisectionError) -> Self {
         match err {
@@ -41,6 +41,10 @@ use crate::ui::Ui;
 ///
 /// If a working-copy commit gets abandoned, it will be given a new, empty
 /// commit. This is true in general; it is not specific to this command.
+///
+/// Bookmarks pointing to the abandoned revisions are deleted by default. If
+/// the `abandon.retain-bookmarks` setting is enabled, or `--retain-bookmarks`
+/// is given, they are moved to the parent revisions instead.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct AbandonArgs {
     /// The revision(s) to abandon (default: @) [aliases: -r]
@@ -58,6 +62,12 @@ pub(crate) struct AbandonArgs {
     #[arg(long)]
     retain_bookmarks: bool,
 
+    /// Delete bookmarks pointing to the revisions to abandon
+    ///
+    /// This overrides the `abandon.retain-bookmarks` setting.
+    #[arg(long, conflicts_with = "retain_bookmarks")]
+    delete_bookmarks: bool,
+
     /// Do not modify the content of the children of the abandoned commits
     #[arg(long)]
     restore_descendants: bool,
@@ -103,9 +113,18 @@ pub(crate) fn cmd_abandon(
         return Ok(());
     }
 
+    let retain_bookmarks = if args.retain_bookmarks {
+        true
+    } else if args.delete_bookmarks {
+        false
+    } else {
+        workspace_command
+            .settings()
+            .get_bool("abandon.retain-bookmarks")?
+    };
     let mut tx = workspace_command.start_transaction();
     let options = RewriteRefsOptions {
-        delete_abandoned_bookmarks: !args.retain_bookmarks,
+        delete_abandoned_bookmarks: !retain_bookmarks,
     };
     let mut num_rebased = 0;
     tx.repo_mut().transform_descendants_with_options(
@@ -126,13 +145,16 @@ pub(crate) fn cmd_abandon(
         },
     )?;
 
-    let deleted_bookmarks = diff_named_ref_targets(
+    let (deleted_bookmarks, moved_bookmarks): (Vec<_>, Vec<_>) = diff_named_ref_targets(
         tx.base_repo().view().local_bookmarks(),
         tx.repo().view().local_bookmarks(),
     )
-    .filter(|(_, (_old, new))| new.is_absent())
-    .map(|(name, _)| name.to_owned())
-    .collect_vec();
+    .filter(|(_, (old, _new))| old.added_ids().any(|id| to_abandon.contains(id)))
+    .partition(|(_, (_old, new))| new.is_absent());
+    let deleted_bookmarks = deleted_bookmarks
+        .into_iter()
+        .map(|(name, _)| name.to_owned())
+        .collect_vec();
 
     if let Some(mut formatter) = ui.status_formatter() {
         writeln!(formatter, "Abandoned {} commits:", to_abandon.len())?;
@@ -152,6 +174,16 @@ pub(crate) fn cmd_abandon(
                 deleted_bookmarks.iter().map(|n| n.as_symbol()).join(", ")
             )?;
         }
+        if !moved_bookmarks.is_empty() {
+            writeln!(
+                formatter,
+                "Moved bookmarks to parents of abandoned commits: {}",
+                moved_bookmarks
+                    .iter()
+                    .map(|(n, _)| n.as_symbol())
+                    .join(", ")
+            )?;
+        }
         if num_rebased > 0 {
             if args.restore_descendants {
                 writeln!(
@@ -91,6 +91,7 @@ pub(crate) fn cmd_absorb(
         &workspace_command,
         &fileset_expression,
         [&source_commit.tree()],
+        &[],
     )?;
 
     let path_converter = workspace_command.path_converter();
@@ -0,0 +1,154 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+use std::io::Read as _;
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::backend::Signature;
+use jj_lib::commit::CommitIteratorExt as _;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::patch::apply_file_patches;
+use jj_lib::patch::parse_patch_series;
+use jj_lib::repo::Repo as _;
+use jj_lib::rewrite::merge_commit_trees;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Apply a patch file to the working copy or as a new commit
+///
+/// The patch can be a plain unified diff or a Git-style patch as produced by
+/// `git diff`, `git format-patch`, or `jj diff --git`. Binary patches are not
+/// supported.
+///
+/// By default, the changes are applied to the working-copy commit. With
+/// `--onto`, a new commit is created on top of the given revisions instead. Its
+/// author and description are taken from the `From:`, `Date:`, and `Subject:`
+/// headers of the patch and the message that follows them, if present.
+///
+/// Several patches can be applied at once, either by passing multiple patch
+/// files or an mbox file as produced by `git format-patch --stdout` or `jj
+/// export --stdout`. With `--onto`, each patch then becomes a separate commit
+/// on top of the previous one.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct ApplyArgs {
+    /// The patch files to apply, or `-` to read a patch from stdin
+    #[arg(required = true, value_name = "PATCHES")]
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    patches: Vec<PathBuf>,
+
+    /// Create a new commit with the patch applied on top of these revisions
+    #[arg(long, short, value_name = "REVSETS")]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
+    onto: Option<Vec<RevisionArg>>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_apply(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ApplyArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let mut patches = vec![];
+    for patch_path in &args.patches {
+        let mut content = vec![];
+        if patch_path.as_os_str() == "-" {
+            io::stdin().read_to_end(&mut content)?;
+        } else {
+            let path = command.cwd().join(patch_path);
+            content = std::fs::read(&path).map_err(|err| {
+                user_error_with_message(
+                    format!("Failed to read patch file {}", patch_path.display()),
+                    err,
+                )
+            })?;
+        }
+        patches.extend(parse_patch_series(&content)?);
+    }
+    if patches.iter().all(|patch| patch.files.is_empty()) {
+        return Err(user_error("No file changes found in the patch"));
+    }
+
+    if let Some(onto) = &args.onto {
+        let parent_commits: Vec<_> = workspace_command
+            .resolve_some_revsets(ui, onto)?
+            .into_iter()
+            .map(|commit_id| workspace_command.repo().store().get_commit(&commit_id))
+            .try_collect()?;
+        let mut tx = workspace_command.start_transaction();
+        let mut parent_ids = parent_commits.iter().ids().cloned().collect_vec();
+        let mut base_tree = merge_commit_trees(tx.repo(), &parent_commits).block_on()?;
+        for patch in patches {
+            let new_tree = apply_file_patches(&base_tree, &patch.files).block_on()?;
+            let mut commit_builder = tx.repo_mut().new_commit(parent_ids, new_tree).detach();
+            commit_builder.set_description(&patch.description);
+            if let Some((name, email)) = patch.author {
+                let timestamp = patch
+                    .author_timestamp
+                    .unwrap_or(commit_builder.author().timestamp);
+                commit_builder.set_author(Signature {
+                    name,
+                    email,
+                    timestamp,
+                });
+            }
+            let new_commit = commit_builder.write(tx.repo_mut())?;
+            if let Some(mut formatter) = ui.status_formatter() {
+                write!(formatter, "Created new commit ")?;
+                tx.write_commit_summary(formatter.as_mut(), &new_commit)?;
+                writeln!(formatter)?;
+            }
+            parent_ids = vec![new_commit.id().clone()];
+            base_tree = new_commit.tree();
+        }
+        tx.finish(ui, "apply patch")?;
+    } else {
+        let wc_commit_id = workspace_command
+            .get_wc_commit_id()
+            .ok_or_else(|| user_error("This command requires a working copy"))?
+            .clone();
+        let wc_commit = workspace_command.repo().store().get_commit(&wc_commit_id)?;
+        workspace_command.check_rewritable([wc_commit.id()])?;
+        let mut tx = workspace_command.start_transaction();
+        let mut new_tree = wc_commit.tree();
+        for patch in &patches {
+            new_tree = apply_file_patches(&new_tree, &patch.files).block_on()?;
+        }
+        tx.repo_mut()
+            .rewrite_commit(&wc_commit)
+            .set_tree(new_tree)
+            .write()?;
+        let num_rebased = tx.repo_mut().rebase_descendants()?;
+        if num_rebased > 0 {
+            writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
+        }
+        tx.finish(
+            ui,
+            format!("apply patch to commit {}", wc_commit.id().hex()),
+        )?;
+    }
+    Ok(())
+}
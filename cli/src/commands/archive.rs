@@ -0,0 +1,393 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+use chrono::Datelike as _;
+use chrono::Timelike as _;
+use clap_complete::ArgValueCompleter;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use futures::StreamExt as _;
+use futures::stream;
+use jj_lib::backend::BackendError;
+use jj_lib::backend::BackendResult;
+use jj_lib::backend::Timestamp;
+use jj_lib::conflicts::ConflictMaterializeOptions;
+use jj_lib::conflicts::MaterializedTreeValue;
+use jj_lib::conflicts::materialize_merge_result_to_bytes;
+use jj_lib::conflicts::materialize_tree_value;
+use jj_lib::file_util::copy_async_to_sync;
+use jj_lib::file_util::persist_temp_file;
+use jj_lib::merge::MergedTreeValue;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
+use pollster::FutureExt as _;
+use tempfile::NamedTempFile;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt as _;
+use tracing::instrument;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+use zip::write::StreamWriter;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::print_unmatched_explicit_paths;
+use crate::command_error::CommandError;
+use crate::command_error::user_error_with_message;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Create an archive of the files in a revision
+///
+/// The archive is created directly from the revision's tree, without touching
+/// the working copy. Conflicted files are archived with conflict markers. Git
+/// submodules are not included.
+///
+/// The format is inferred from the extension of the output file (`.tar`,
+/// `.tar.gz`, `.tgz`, or `.zip`) unless `--format` is specified. It defaults to
+/// `tar` if the extension isn't recognized.
+///
+/// Example: `jj archive -r v1.0 --prefix project-1.0/ -o project-1.0.tar.gz`
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct ArchiveArgs {
+    /// The revision to archive
+    #[arg(long, short, default_value = "@", value_name = "REVSET")]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
+    revision: RevisionArg,
+
+    /// The file to write the archive to, or `-` to write it to stdout
+    #[arg(long, short, value_name = "FILE")]
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    output: PathBuf,
+
+    /// The archive format
+    #[arg(long, value_enum)]
+    format: Option<ArchiveFormat>,
+
+    /// String to prepend to each path in the archive
+    ///
+    /// Add a trailing `/` to put the files in a directory, e.g.
+    /// `--prefix project-1.0/`.
+    #[arg(long)]
+    prefix: Option<String>,
+
+    /// Only archive files matching these filesets (instead of all files)
+    #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
+    #[arg(add = ArgValueCompleter::new(complete::all_revision_files))]
+    paths: Vec<String>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum ArchiveFormat {
+    /// Uncompressed tarball
+    Tar,
+    /// Gzip-compressed tarball
+    #[value(name = "tar.gz", alias = "tgz")]
+    TarGz,
+    /// Zip archive
+    Zip,
+}
+
+impl ArchiveFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// Files up to this size are read into memory instead of being read twice.
+const MAX_BUFFERED_FILE_SIZE: u64 = 1 << 20;
+
+/// Materialized tree entry to add to the archive.
+enum ArchiveEntry {
+    File {
+        reader: Pin<Box<dyn AsyncRead + Send>>,
+        size: u64,
+        executable: bool,
+    },
+    Symlink {
+        target: String,
+    },
+    /// Entry that can't be archived, with the reason why.
+    Unsupported(String),
+    /// Entry that is intentionally left out of the archive.
+    Excluded,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_archive(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ArchiveArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    let tree = commit.tree();
+    let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
+    let matcher = fileset_expression.to_matcher();
+    let format = args
+        .format
+        .or_else(|| ArchiveFormat::from_path(&args.output))
+        .unwrap_or(ArchiveFormat::Tar);
+
+    // The archive is written to a temporary file, which replaces the output
+    // file only once the archive is complete.
+    let output_file = if args.output.as_os_str() == "-" {
+        None
+    } else {
+        let path = command.cwd().join(&args.output);
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let temp_file = NamedTempFile::new_in(dir).map_err(|err| {
+            user_error_with_message(format!("Failed to create {}", path.display()), err)
+        })?;
+        Some((temp_file, path))
+    };
+    let output: Box<dyn Write + '_> = match &output_file {
+        None => Box::new(ui.stdout_formatter()),
+        Some((temp_file, _)) => Box::new(io::BufWriter::new(temp_file.as_file())),
+    };
+    let mut archive = ArchiveWriter::new(output, format, &commit.committer().timestamp);
+    let write_error = |err| user_error_with_message("Failed to write archive", err);
+
+    let repo = workspace_command.repo();
+    let store = repo.store();
+    let options = ConflictMaterializeOptions {
+        marker_style: workspace_command.env().conflict_marker_style(),
+        marker_len: None,
+        merge: store.merge_options().clone(),
+    };
+    let read_entry = async |path: RepoPathBuf, value: BackendResult<MergedTreeValue>| {
+        let materialized = materialize_tree_value(store, &path, value?, tree.labels()).await?;
+        let entry = match materialized {
+            MaterializedTreeValue::Absent => panic!("absent values should be excluded"),
+            MaterializedTreeValue::File(file) => {
+                // Tar headers need the size before the contents. Small files
+                // are buffered in memory. Larger files are read once to count
+                // the remaining bytes, and read again when archived.
+                let read_error = |err: io::Error| BackendError::ReadFile {
+                    path: path.clone(),
+                    id: file.id.clone(),
+                    source: err.into(),
+                };
+                let mut content = Vec::new();
+                let mut reader = file.reader.take(MAX_BUFFERED_FILE_SIZE + 1);
+                reader.read_to_end(&mut content).await.map_err(read_error)?;
+                if content.len() as u64 <= MAX_BUFFERED_FILE_SIZE {
+                    ArchiveEntry::File {
+                        size: content.len() as u64,
+                        reader: Box::pin(io::Cursor::new(content)),
+                        executable: file.executable,
+                    }
+                } else {
+                    let remaining = copy_async_to_sync(reader.into_inner(), &mut io::sink())
+                        .await
+                        .map_err(read_error)?;
+                    ArchiveEntry::File {
+                        reader: store.read_file(&path, &file.id).await?,
+                        size: (content.len() + remaining) as u64,
+                        executable: file.executable,
+                    }
+                }
+            }
+            MaterializedTreeValue::Symlink { target, .. } => ArchiveEntry::Symlink { target },
+            MaterializedTreeValue::FileConflict(file) => {
+                let content: Vec<u8> =
+                    materialize_merge_result_to_bytes(&file.contents, &file.labels, &options)
+                        .into();
+                ArchiveEntry::File {
+                    size: content.len() as u64,
+                    reader: Box::pin(io::Cursor::new(content)),
+                    executable: file.executable.unwrap_or(false),
+                }
+            }
+            MaterializedTreeValue::AccessDenied(err) => {
+                ArchiveEntry::Unsupported(format!("permission error: {err}"))
+            }
+            MaterializedTreeValue::OtherConflict { .. } => {
+                ArchiveEntry::Unsupported("conflict that can't be materialized".to_owned())
+            }
+            MaterializedTreeValue::GitSubmodule(_) => ArchiveEntry::Excluded,
+            MaterializedTreeValue::Tree(_) => panic!("entries should not contain trees"),
+        };
+        BackendResult::Ok((path, entry))
+    };
+    let mut entries = stream::iter(tree.entries_matching(matcher.as_ref()))
+        .map(|(path, value)| read_entry(path, value))
+        .buffered(store.concurrency().max(1));
+    while let Some(result) = entries.next().block_on() {
+        let (path, entry) = result?;
+        let name = format!(
+            "{}{}",
+            args.prefix.as_deref().unwrap_or_default(),
+            path.as_internal_file_string()
+        );
+        match entry {
+            ArchiveEntry::File {
+                reader,
+                size,
+                executable,
+            } => archive
+                .append_file(&name, reader, size, executable)
+                .map_err(write_error)?,
+            ArchiveEntry::Symlink { target } => archive
+                .append_symlink(&name, &target)
+                .map_err(write_error)?,
+            ArchiveEntry::Unsupported(reason) => {
+                let ui_path = workspace_command.format_file_path(&path);
+                writeln!(ui.warning_default(), "Skipping '{ui_path}' due to {reason}")?;
+            }
+            ArchiveEntry::Excluded => {}
+        }
+    }
+    drop(entries);
+    archive.finish().map_err(write_error)?;
+    if let Some((temp_file, path)) = output_file {
+        persist_temp_file(temp_file, &path).map_err(|err| {
+            user_error_with_message(format!("Failed to create {}", path.display()), err)
+        })?;
+    }
+    print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree], &[])?;
+    Ok(())
+}
+
+enum ArchiveWriter<W: Write> {
+    Tar(tar::Builder<W>, u64),
+    TarGz(Box<tar::Builder<GzEncoder<W>>>, u64),
+    Zip(Box<ZipWriter<StreamWriter<W>>>, zip::DateTime),
+}
+
+impl<W: Write> ArchiveWriter<W> {
+    fn new(output: W, format: ArchiveFormat, timestamp: &Timestamp) -> Self {
+        let mtime = timestamp.timestamp.0.div_euclid(1000).max(0) as u64;
+        match format {
+            ArchiveFormat::Tar => Self::Tar(tar::Builder::new(output), mtime),
+            ArchiveFormat::TarGz => Self::TarGz(
+                Box::new(tar::Builder::new(GzEncoder::new(
+                    output,
+                    Compression::default(),
+                ))),
+                mtime,
+            ),
+            ArchiveFormat::Zip => {
+                // Zip timestamps are in local time
+                let datetime = timestamp
+                    .to_datetime()
+                    .ok()
+                    .and_then(|datetime| {
+                        zip::DateTime::from_date_and_time(
+                            datetime.year().try_into().ok()?,
+                            datetime.month() as u8,
+                            datetime.day() as u8,
+                            datetime.hour() as u8,
+                            datetime.minute() as u8,
+                            datetime.second() as u8,
+                        )
+                        .ok()
+                    })
+                    .unwrap_or_default();
+                Self::Zip(Box::new(ZipWriter::new_stream(output)), datetime)
+            }
+        }
+    }
+
+    fn append_file(
+        &mut self,
+        name: &str,
+        reader: Pin<Box<dyn AsyncRead + Send>>,
+        size: u64,
+        executable: bool,
+    ) -> io::Result<()> {
+        let mode = if executable { 0o755 } else { 0o644 };
+        match self {
+            Self::Tar(builder, mtime) => {
+                let mut header = tar_header(tar::EntryType::Regular, mode, *mtime);
+                header.set_size(size);
+                builder.append_data(&mut header, name, BlockingReader(reader))
+            }
+            Self::TarGz(builder, mtime) => {
+                let mut header = tar_header(tar::EntryType::Regular, mode, *mtime);
+                header.set_size(size);
+                builder.append_data(&mut header, name, BlockingReader(reader))
+            }
+            Self::Zip(writer, datetime) => {
+                let options = SimpleFileOptions::default()
+                    .last_modified_time(*datetime)
+                    .unix_permissions(mode)
+                    .large_file(size >= u64::from(u32::MAX));
+                writer.start_file(name, options)?;
+                copy_async_to_sync(reader, writer.as_mut()).block_on()?;
+                Ok(())
+            }
+        }
+    }
+
+    fn append_symlink(&mut self, name: &str, target: &str) -> io::Result<()> {
+        match self {
+            Self::Tar(builder, mtime) => {
+                let mut header = tar_header(tar::EntryType::Symlink, 0o777, *mtime);
+                builder.append_link(&mut header, name, target)
+            }
+            Self::TarGz(builder, mtime) => {
+                let mut header = tar_header(tar::EntryType::Symlink, 0o777, *mtime);
+                builder.append_link(&mut header, name, target)
+            }
+            Self::Zip(writer, datetime) => {
+                let options = SimpleFileOptions::default().last_modified_time(*datetime);
+                Ok(writer.add_symlink(name, target, options)?)
+            }
+        }
+    }
+
+    fn finish(self) -> io::Result<()> {
+        let mut output = match self {
+            Self::Tar(builder, _) => builder.into_inner()?,
+            Self::TarGz(builder, _) => (*builder).into_inner()?.finish()?,
+            Self::Zip(writer, _) => (*writer).finish()?.into_inner(),
+        };
+        output.flush()
+    }
+}
+
+/// `Read` implementation that blocks on an `AsyncRead`.
+struct BlockingReader(Pin<Box<dyn AsyncRead + Send>>);
+
+impl Read for BlockingReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf).block_on()
+    }
+}
+
+fn tar_header(entry_type: tar::EntryType, mode: u32, mtime: u64) -> tar::Header {
+    let mut header = tar::Header::new_gnu();
+    header.set_entry_type(entry_type);
+    header.set_mode(mode);
+    header.set_mtime(mtime);
+    header.set_size(0);
+    header
+}
@@ -17,6 +17,8 @@ use clap_complete::ArgValueCompleter;
 use indexmap::IndexSet;
 use itertools::Itertools as _;
 use jj_lib::copies::CopyRecords;
+use jj_lib::fileset::FilesetExpression;
+use jj_lib::matchers::EverythingMatcher;
 use jj_lib::merge::Diff;
 use jj_lib::repo::Repo as _;
 use jj_lib::rewrite::merge_commit_trees;
@@ -88,6 +90,14 @@ pub(crate) struct DiffArgs {
     #[arg(add = ArgValueCompleter::new(complete::modified_revision_or_range_files))]
     paths: Vec<String>,
 
+    /// Also show the files the given paths were renamed or copied to or from
+    ///
+    /// By default, a renamed file is only shown as renamed if its new path
+    /// matches the given paths. With this flag, the files renamed from the
+    /// given paths are shown as well.
+    #[arg(long, requires = "paths")]
+    follow: bool,
+
     /// Render each file diff entry using the given template
     ///
     /// All 0-argument methods of the [`TreeDiffEntry` type] are available as
@@ -121,11 +131,11 @@ pub(crate) fn cmd_diff(
     let workspace_command = command.workspace_helper(ui)?;
     let repo = workspace_command.repo();
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
-    let matcher = fileset_expression.to_matcher();
+    let mut matcher = fileset_expression.to_matcher();
 
     let from_tree;
     let to_tree;
-    let mut copy_records = CopyRecords::default();
+    let mut copy_ranges = vec![];
     if args.from.is_some() || args.to.is_some() {
         let resolve_revision = |r: &Option<RevisionArg>| {
             workspace_command.resolve_single_rev(ui, r.as_ref().unwrap_or(&RevisionArg::AT))
@@ -134,9 +144,7 @@ pub(crate) fn cmd_diff(
         let to = resolve_revision(&args.to)?;
         from_tree = from.tree();
         to_tree = to.tree();
-
-        let records = get_copy_records(repo.store(), from.id(), to.id(), &matcher)?;
-        copy_records.add_records(records)?;
+        copy_ranges.push((from.id().clone(), to.id().clone()));
     } else {
         let revision_args = args
             .revisions
@@ -178,10 +186,35 @@ pub(crate) fn cmd_diff(
 
         for p in &parents {
             for to in &heads {
-                let records = get_copy_records(repo.store(), p.id(), to.id(), &matcher)?;
-                copy_records.add_records(records)?;
+                copy_ranges.push((p.id().clone(), to.id().clone()));
+            }
+        }
+    }
+
+    if args.follow {
+        // Include the targets of copies whose source matches the paths.
+        let mut target_paths = vec![];
+        for (from_id, to_id) in &copy_ranges {
+            for record in get_copy_records(repo.store(), from_id, to_id, &EverythingMatcher)? {
+                let record = record?;
+                if matcher.matches(&record.source) && !matcher.matches(&record.target) {
+                    target_paths.push(FilesetExpression::file_path(record.target));
+                }
             }
         }
+        if !target_paths.is_empty() {
+            matcher = FilesetExpression::union_all(
+                std::iter::once(fileset_expression.clone())
+                    .chain(target_paths)
+                    .collect(),
+            )
+            .to_matcher();
+        }
+    }
+    let mut copy_records = CopyRecords::default();
+    for (from_id, to_id) in &copy_ranges {
+        let records = get_copy_records(repo.store(), from_id, to_id, &matcher)?;
+        copy_records.add_records(records)?;
     }
 
     // -T disables both short/long rendering formats, but it might be okay to
@@ -221,6 +254,7 @@ pub(crate) fn cmd_diff(
         &workspace_command,
         &fileset_expression,
         [&from_tree, &to_tree],
+        &[],
     )?;
     Ok(())
 }
@@ -0,0 +1,240 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::patch::encode_display_name;
+use jj_lib::patch::encode_header_value;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathUiConverter;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::short_commit_hash;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::complete;
+use crate::diff_util::DiffFormat;
+use crate::diff_util::DiffRenderer;
+use crate::diff_util::UnifiedDiffOptions;
+use crate::formatter::Formatter;
+use crate::formatter::PlainTextFormatter;
+use crate::ui::Ui;
+
+/// Export revisions as patches
+///
+/// Each revision is written to a numbered patch file named after its subject,
+/// e.g. `0001-fix-the-bug.patch`, in the format produced by `git
+/// format-patch`. The patches contain the author, date, and description of the
+/// revisions, so they can be sent to a mailing list and applied with `jj apply
+/// --onto` or `git am`.
+///
+/// The revisions are exported in topological order, oldest first. Merge
+/// commits can't be exported.
+///
+/// Descriptions aren't escaped. If a description contains a line consisting of
+/// `---`, or starting with `--- ` or `diff --git `, the description is cut
+/// there when the patch is applied.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct ExportArgs {
+    /// The revisions to export
+    #[arg(long, short, required = true, value_name = "REVSETS")]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
+    revisions: Vec<RevisionArg>,
+
+    /// The format to export the revisions in
+    #[arg(long, value_enum, default_value_t = ExportFormat::GitPatch)]
+    format: ExportFormat,
+
+    /// Directory to write the patch files to (instead of the current
+    /// directory)
+    #[arg(long, short, value_name = "DIR", conflicts_with = "stdout")]
+    #[arg(value_hint = clap::ValueHint::DirPath)]
+    output_dir: Option<PathBuf>,
+
+    /// Write all patches to stdout in mbox format instead of creating files
+    #[arg(long)]
+    stdout: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum ExportFormat {
+    /// Email patches as produced by `git format-patch`
+    GitPatch,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_export(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ExportArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let mut commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    commits.reverse();
+    for commit in &commits {
+        if commit.id() == repo.store().root_commit_id() {
+            return Err(user_error("Cannot export the root commit"));
+        }
+        if commit.parent_ids().len() > 1 {
+            return Err(user_error(format!(
+                "Cannot export merge commit {}",
+                short_commit_hash(commit.id())
+            )));
+        }
+    }
+    if commits.is_empty() {
+        writeln!(ui.status(), "No revisions to export")?;
+        return Ok(());
+    }
+
+    // Paths in patches are relative to the workspace root
+    let workspace_root = workspace_command.workspace_root();
+    let path_converter = RepoPathUiConverter::Fs {
+        cwd: workspace_root.to_owned(),
+        base: workspace_root.to_owned(),
+    };
+    let marker_style = workspace_command.env().conflict_marker_style();
+    let stat_renderer = DiffRenderer::new(
+        repo.as_ref(),
+        &path_converter,
+        marker_style,
+        vec![DiffFormat::Stat(Box::default())],
+    );
+    let git_options = UnifiedDiffOptions::from_settings(workspace_command.settings())?;
+    let git_renderer = DiffRenderer::new(
+        repo.as_ref(),
+        &path_converter,
+        marker_style,
+        vec![DiffFormat::Git(Box::new(git_options))],
+    );
+    let write_git_patch = |formatter: &mut dyn Formatter, commit: &Commit, index: usize| {
+        let numbering = if commits.len() > 1 {
+            format!(" {}/{}", index + 1, commits.len())
+        } else {
+            String::new()
+        };
+        let (subject, body) = split_description(commit.description());
+        let author = commit.author();
+        let date = author
+            .timestamp
+            .to_datetime()
+            .map_err(|err| user_error_with_message("Invalid author date", err))?;
+        writeln!(
+            formatter,
+            "From {} Mon Sep 17 00:00:00 2001",
+            commit.id().hex()
+        )?;
+        writeln!(
+            formatter,
+            "From: {} <{}>",
+            encode_display_name(&author.name),
+            author.email
+        )?;
+        writeln!(formatter, "Date: {}", date.to_rfc2822())?;
+        writeln!(
+            formatter,
+            "Subject: [PATCH{numbering}] {}",
+            encode_header_value(subject)
+        )?;
+        writeln!(formatter)?;
+        if !body.is_empty() {
+            writeln!(formatter, "{body}")?;
+        }
+        writeln!(formatter, "---")?;
+        stat_renderer
+            .show_patch(ui, formatter, commit, &EverythingMatcher, 72)
+            .block_on()?;
+        writeln!(formatter)?;
+        git_renderer
+            .show_patch(ui, formatter, commit, &EverythingMatcher, 72)
+            .block_on()?;
+        writeln!(formatter, "-- ")?;
+        writeln!(formatter, "jj {}", env!("CARGO_PKG_VERSION"))?;
+        writeln!(formatter)?;
+        Ok::<_, CommandError>(())
+    };
+    let write_patch =
+        |formatter: &mut dyn Formatter, commit: &Commit, index: usize| match args.format {
+            ExportFormat::GitPatch => write_git_patch(formatter, commit, index),
+        };
+
+    if args.stdout {
+        // Patches are written without color so that they can be applied
+        let mut formatter = PlainTextFormatter::new(ui.stdout());
+        for (index, commit) in commits.iter().enumerate() {
+            write_patch(&mut formatter, commit, index)?;
+        }
+        return Ok(());
+    }
+    let output_dir = command
+        .cwd()
+        .join(args.output_dir.as_deref().unwrap_or("".as_ref()));
+    fs::create_dir_all(&output_dir).map_err(|err| {
+        user_error_with_message(format!("Failed to create {}", output_dir.display()), err)
+    })?;
+    for (index, commit) in commits.iter().enumerate() {
+        let (subject, _) = split_description(commit.description());
+        let path = output_dir.join(patch_file_name(index + 1, subject));
+        let write_error =
+            |err| user_error_with_message(format!("Failed to write {}", path.display()), err);
+        let file = File::create(&path).map_err(write_error)?;
+        let mut formatter = PlainTextFormatter::new(io::BufWriter::new(file));
+        write_patch(&mut formatter, commit, index)?;
+        formatter.flush().map_err(write_error)?;
+        let relative_path = path.strip_prefix(command.cwd()).unwrap_or(&path);
+        writeln!(ui.stdout(), "{}", relative_path.display())?;
+    }
+    Ok(())
+}
+
+/// Splits a description into its first line and the rest.
+fn split_description(description: &str) -> (&str, &str) {
+    let (subject, body) = description.split_once('\n').unwrap_or((description, ""));
+    (subject.trim(), body.trim())
+}
+
+/// Returns a file name like `0001-fix-the-bug.patch` for the given subject.
+fn patch_file_name(number: usize, subject: &str) -> String {
+    let slug = subject
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '.' && c != '_')
+        .filter(|word| !word.is_empty())
+        .join("-");
+    let slug = slug.trim_matches('.');
+    let slug = match slug.char_indices().nth(52) {
+        Some((end, _)) => slug[..end].trim_end_matches(['-', '.']),
+        None => slug,
+    };
+    if slug.is_empty() {
+        format!("{number:04}.patch")
+    } else {
+        format!("{number:04}-{slug}.patch")
+    }
+}
@@ -12,8 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
+
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
@@ -26,6 +30,9 @@ use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
 /// List files in a revision
+///
+/// With `--no-recurse`, the entries of the given directories are listed
+/// instead, like `ls`. Subdirectories are listed as entries of `tree` type.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct FileListArgs {
     /// The revision to list files in
@@ -52,6 +59,12 @@ pub(crate) struct FileListArgs {
     #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
     #[arg(add = ArgValueCompleter::new(complete::all_revision_files))]
     paths: Vec<String>,
+
+    /// List the entries of the given directories instead of the files in them
+    ///
+    /// If no paths are given, the entries of the root directory are listed.
+    #[arg(long)]
+    no_recurse: bool,
 }
 
 #[instrument(skip_all)]
@@ -78,13 +91,49 @@ pub(crate) fn cmd_file_list(
 
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
-    for (path, value) in tree.entries_matching(matcher.as_ref()) {
-        let entry = TreeEntry {
-            path,
-            value: value?,
-        };
-        template.format(&entry, formatter.as_mut())?;
+    if args.no_recurse {
+        let mut dirs = HashSet::new();
+        if args.paths.is_empty() {
+            dirs.insert(RepoPath::root());
+        }
+        for path in fileset_expression.explicit_paths() {
+            if tree.path_value(path)?.is_tree() {
+                dirs.insert(path);
+            }
+        }
+        let mut last_path: Option<RepoPathBuf> = None;
+        for (path, value) in tree.entries_matching(matcher.as_ref()) {
+            // Map the file to the entry of the innermost listed directory
+            // containing it.
+            let entry_path = path
+                .ancestors()
+                .find(|ancestor| ancestor.parent().is_some_and(|dir| dirs.contains(dir)))
+                .unwrap_or(&path)
+                .to_owned();
+            if last_path.as_ref() == Some(&entry_path) {
+                continue;
+            }
+            let value = if entry_path == path {
+                value?
+            } else {
+                tree.path_value(&entry_path)?
+            };
+            let entry = TreeEntry {
+                path: entry_path.clone(),
+                value,
+            };
+            template.format(&entry, formatter.as_mut())?;
+            last_path = Some(entry_path);
+        }
+    } else {
+        for (path, value) in tree.entries_matching(matcher.as_ref()) {
+            let entry = TreeEntry {
+                path,
+                value: value?,
+            };
+            template.format(&entry, formatter.as_mut())?;
+        }
     }
-    print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree])?;
+    print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree], &[])?;
     Ok(())
 }
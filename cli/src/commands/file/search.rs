@@ -12,49 +12,90 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use bstr::BString;
 use clap_complete::ArgValueCompleter;
+use futures::StreamExt as _;
+use futures::stream;
+use itertools::Itertools as _;
+use jj_lib::backend::BackendResult;
+use jj_lib::commit::Commit;
 use jj_lib::conflicts::MaterializedTreeValue;
 use jj_lib::conflicts::materialize_tree_value;
+use jj_lib::matchers::IntersectionMatcher;
+use jj_lib::matchers::PrefixMatcher;
+use jj_lib::merge::MergedTreeValue;
 use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::str_util::StringPattern;
+use jj_lib::str_util::StringPatternParseError;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::print_unmatched_explicit_paths;
+use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
 use crate::command_error::cli_error;
+use crate::command_error::user_error;
 use crate::complete;
 use crate::ui::Ui;
 
 /// Search for content in files
 ///
-/// Lists files containing the specified pattern.
+/// Lists files containing the specified pattern. With `--lines`, the matching
+/// lines are printed as well.
 ///
-/// This is an early version of the command. It only supports glob matching for
-/// now, it doesn't search files concurrently, and it doesn't indicate where in
-/// the file the match was found.
+/// If more than one revision is searched, each result is prefixed with the
+/// commit ID of the revision it was found in. Only files matching the sparse
+/// patterns of the current workspace are searched. For conflicted files, the
+/// pattern is matched against each side of the conflict.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct FileSearchArgs {
-    /// The revision to search files in
-    #[arg(long, short, default_value = "@", value_name = "REVSET")]
+    /// The revisions to search files in
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSETS",
+        alias = "revision"
+    )]
     #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
-    revision: RevisionArg,
+    revisions: Vec<RevisionArg>,
 
-    /// The glob pattern to search for
+    /// The pattern to search for
     ///
-    /// The whole line must match the pattern, so you may want to pass something
-    /// like `--pattern '*foo*'`.
+    /// The pattern is matched against each line. By default, the pattern is a
+    /// glob that must match the whole line, so you may want to pass something
+    /// like `--pattern '*foo*'`. Other [string pattern] kinds can be selected
+    /// with a prefix, e.g. `--pattern 'regex:fo+'` or
+    /// `--pattern 'substring:foo'`.
+    ///
+    /// [string pattern]:
+    ///     https://docs.jj-vcs.dev/latest/revsets/#string-patterns
     #[arg(long, short, value_name = "PATTERN")]
     pattern: String,
 
+    /// Print the matching lines with their line numbers
+    #[arg(long)]
+    lines: bool,
+
     /// Only search files matching these prefixes (instead of all files)
     #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
     #[arg(add = ArgValueCompleter::new(complete::all_revision_files))]
     paths: Vec<String>,
 }
 
+/// Contents of a file to search.
+enum SearchContents {
+    /// File contents, or the sides of a conflicted file.
+    Contents(Vec<BString>),
+    /// File that can't be read.
+    AccessDenied(String),
+    /// Entry that isn't searched, such as a symlink.
+    Skipped,
+}
+
 #[instrument(skip_all)]
 pub(crate) fn cmd_file_search(
     ui: &mut Ui,
@@ -62,57 +103,101 @@ pub(crate) fn cmd_file_search(
     args: &FileSearchArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
-    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
-    let tree = commit.tree();
+    let commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    if commits.is_empty() {
+        return Err(user_error("Empty revision set"));
+    }
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
-    let file_matcher = fileset_expression.to_matcher();
+    let file_matcher = IntersectionMatcher::new(
+        fileset_expression.to_matcher(),
+        PrefixMatcher::new(workspace_command.working_copy().sparse_patterns()?),
+    );
+    let pattern = parse_search_pattern(&args.pattern).map_err(|err| cli_error(err.to_string()))?;
+    let pattern_matcher = pattern.to_matcher();
 
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
     let store = workspace_command.repo().store().clone();
-
-    // TODO: Support other patterns than glob
-    let pattern = StringPattern::glob(&args.pattern).map_err(|err| cli_error(err.to_string()))?;
-    let pattern_matcher = pattern.to_matcher();
-    // TODO: Read files concurrently (depending on backend)
-    for (path, value) in tree.entries_matching(file_matcher.as_ref()) {
-        let value = value?;
-        let materialized =
-            materialize_tree_value(store.as_ref(), &path, value, tree.labels()).block_on()?;
-        match materialized {
-            MaterializedTreeValue::Absent => panic!("Entry for absent path in file listing"),
-            MaterializedTreeValue::AccessDenied(error) => {
-                let ui_path = workspace_command.format_file_path(&path);
-                writeln!(
-                    ui.warning_default(),
-                    "Skipping '{ui_path}' due to permission error: {error}"
-                )?;
-            }
-            MaterializedTreeValue::File(mut materialized_file_value) => {
-                let content = materialized_file_value.read_all(&path).block_on()?;
-                // TODO: Make output templated
-                let ui_path = workspace_command.format_file_path(&path);
-                if let Some(_line) = pattern_matcher.match_lines(&content).next() {
-                    // TODO: Optionally also print the line and line number
-                    writeln!(formatter, "{ui_path}")?;
+    for commit in &commits {
+        let prefix = if commits.len() > 1 {
+            format!("{}:", short_commit_hash(commit.id()))
+        } else {
+            String::new()
+        };
+        let tree = commit.tree();
+        let read_contents = async |path: RepoPathBuf, value: BackendResult<MergedTreeValue>| {
+            let materialized =
+                materialize_tree_value(store.as_ref(), &path, value?, tree.labels()).await?;
+            let contents = match materialized {
+                MaterializedTreeValue::Absent => panic!("Entry for absent path in file listing"),
+                MaterializedTreeValue::AccessDenied(error) => {
+                    SearchContents::AccessDenied(error.to_string())
                 }
-            }
-            MaterializedTreeValue::Symlink { .. } => {}
-            MaterializedTreeValue::FileConflict(materialized_file_value) => {
-                let ui_path = workspace_command.format_file_path(&path);
-                for content in materialized_file_value.contents.adds() {
-                    if let Some(_line) = pattern_matcher.match_lines(content).next() {
-                        // TODO: Optionally also print the conflict side, line and line number
-                        writeln!(formatter, "{ui_path}")?;
-                        break;
+                MaterializedTreeValue::File(mut file) => {
+                    SearchContents::Contents(vec![file.read_all(&path).await?.into()])
+                }
+                MaterializedTreeValue::FileConflict(file) => {
+                    SearchContents::Contents(file.contents.adds().cloned().collect())
+                }
+                MaterializedTreeValue::Symlink { .. }
+                | MaterializedTreeValue::OtherConflict { .. }
+                | MaterializedTreeValue::GitSubmodule(_) => SearchContents::Skipped,
+                MaterializedTreeValue::Tree(_) => panic!("Entry for tree in file listing"),
+            };
+            BackendResult::Ok((path, contents))
+        };
+        let mut results = stream::iter(tree.entries_matching(&file_matcher))
+            .map(|(path, value)| read_contents(path, value))
+            .buffered(store.concurrency().max(1));
+        while let Some(result) = results.next().block_on() {
+            let (path, contents) = result?;
+            let ui_path = workspace_command.format_file_path(&path);
+            let contents = match contents {
+                SearchContents::Contents(contents) => contents,
+                SearchContents::AccessDenied(error) => {
+                    writeln!(
+                        ui.warning_default(),
+                        "Skipping '{ui_path}' due to permission error: {error}"
+                    )?;
+                    continue;
+                }
+                SearchContents::Skipped => continue,
+            };
+            if args.lines {
+                for content in &contents {
+                    for (index, line) in content.split_inclusive(|b| *b == b'\n').enumerate() {
+                        let line = line.strip_suffix(b"\n").unwrap_or(line);
+                        if pattern_matcher.is_match_bytes(line) {
+                            write!(formatter, "{prefix}{ui_path}:{}:", index + 1)?;
+                            formatter.write_all(line)?;
+                            writeln!(formatter)?;
+                        }
                     }
                 }
+            } else if contents
+                .iter()
+                .any(|content| pattern_matcher.match_lines(content).next().is_some())
+            {
+                writeln!(formatter, "{prefix}{ui_path}")?;
             }
-            MaterializedTreeValue::OtherConflict { .. } => {}
-            MaterializedTreeValue::GitSubmodule(_) => {}
-            MaterializedTreeValue::Tree(_) => panic!("Entry for tree in file listing"),
         }
+        drop(results);
     }
-    print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree])?;
+    let trees = commits.iter().map(|commit| commit.tree()).collect_vec();
+    print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, &trees, &[])?;
     Ok(())
 }
+
+/// Parses `kind:pattern`, or a glob pattern if no known kind is specified.
+fn parse_search_pattern(src: &str) -> Result<StringPattern, StringPatternParseError> {
+    if let Some((kind, pattern)) = src.split_once(':') {
+        match StringPattern::from_str_kind(pattern, kind) {
+            Err(StringPatternParseError::InvalidKind(_)) => {}
+            result => return result,
+        }
+    }
+    StringPattern::glob(src)
+}
@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io::Write as _;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
 
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
@@ -37,6 +41,7 @@ use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::print_unmatched_explicit_paths;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
 use crate::commit_templater::TreeEntry;
 use crate::complete;
 use crate::templater::TemplateRenderer;
@@ -46,6 +51,11 @@ use crate::ui::Ui;
 ///
 /// If the given path is a directory, files in the directory will be visited
 /// recursively.
+///
+/// With `--output-dir`, the files are written into the given directory
+/// instead, preserving their paths relative to the workspace root. Use
+/// `--template` to print a header before the content of each file, e.g.
+/// `-T '"==> " ++ path ++ " <==\n"'`.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct FileShowArgs {
     /// The revision to get the file contents from
@@ -74,6 +84,13 @@ pub(crate) struct FileShowArgs {
     #[arg(required = true, value_name = "FILESETS", value_hint = clap::ValueHint::FilePath)]
     #[arg(add = ArgValueCompleter::new(complete::all_revision_files))]
     paths: Vec<String>,
+
+    /// Write the files into this directory instead of printing them
+    ///
+    /// The directory is created if it doesn't exist. Existing files are
+    /// overwritten.
+    #[arg(long, value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
+    output_dir: Option<PathBuf>,
 }
 
 #[instrument(skip_all)]
@@ -88,6 +105,7 @@ pub(crate) fn cmd_file_show(
     // TODO: No need to add special case for empty paths when switching to
     // parse_union_filesets(). paths = [] should be "none()" if supported.
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
+    let output_dir = args.output_dir.as_ref().map(|dir| command.cwd().join(dir));
     let template = {
         let language = workspace_command.commit_template_language();
         let text = match &args.template {
@@ -112,7 +130,14 @@ pub(crate) fn cmd_file_show(
                 path: path.to_owned(),
                 value,
             };
-            write_tree_entries(ui, &workspace_command, &template, &tree, [Ok(entry)])?;
+            write_tree_entries(
+                ui,
+                &workspace_command,
+                &template,
+                &tree,
+                output_dir.as_deref(),
+                [Ok(entry)],
+            )?;
             return Ok(());
         }
     }
@@ -124,11 +149,12 @@ pub(crate) fn cmd_file_show(
         &workspace_command,
         &template,
         &tree,
+        output_dir.as_deref(),
         tree.entries_matching(matcher.as_ref())
             .map(|(path, value)| Ok((path, value?)))
             .map_ok(|(path, value)| TreeEntry { path, value }),
     )?;
-    print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree])?;
+    print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree], &[])?;
     Ok(())
 }
 
@@ -149,6 +175,7 @@ fn write_tree_entries(
     workspace_command: &WorkspaceCommandHelper,
     template: &TemplateRenderer<TreeEntry>,
     tree: &MergedTree,
+    output_dir: Option<&Path>,
     entries: impl IntoIterator<Item = BackendResult<TreeEntry>>,
 ) -> Result<(), CommandError> {
     let repo = workspace_command.repo();
@@ -168,7 +195,8 @@ fn write_tree_entries(
                 )?;
             }
             MaterializedTreeValue::File(file) => {
-                copy_async_to_sync(file.reader, ui.stdout_formatter().as_mut()).block_on()?;
+                let mut output = open_output(ui, output_dir, &entry.path, file.executable)?;
+                copy_async_to_sync(file.reader, &mut output).block_on()?;
             }
             MaterializedTreeValue::FileConflict(file) => {
                 let options = ConflictMaterializeOptions {
@@ -176,15 +204,12 @@ fn write_tree_entries(
                     marker_len: None,
                     merge: repo.store().merge_options().clone(),
                 };
-                materialize_merge_result(
-                    &file.contents,
-                    &file.labels,
-                    &mut ui.stdout_formatter(),
-                    &options,
-                )?;
+                let executable = file.executable.unwrap_or(false);
+                let mut output = open_output(ui, output_dir, &entry.path, executable)?;
+                materialize_merge_result(&file.contents, &file.labels, &mut output, &options)?;
             }
             MaterializedTreeValue::OtherConflict { id, labels } => {
-                ui.stdout_formatter()
+                open_output(ui, output_dir, &entry.path, false)?
                     .write_all(id.describe(&labels).as_bytes())?;
             }
             MaterializedTreeValue::Symlink { .. } | MaterializedTreeValue::GitSubmodule(_) => {
@@ -199,3 +224,32 @@ fn write_tree_entries(
     }
     Ok(())
 }
+
+/// Returns a writer to the file at `path` in `output_dir`, or to stdout if no
+/// output directory is specified.
+fn open_output<'a>(
+    ui: &'a Ui,
+    output_dir: Option<&Path>,
+    path: &RepoPath,
+    executable: bool,
+) -> Result<Box<dyn Write + 'a>, CommandError> {
+    let Some(output_dir) = output_dir else {
+        return Ok(Box::new(ui.stdout_formatter()));
+    };
+    let disk_path = path.to_fs_path(output_dir).map_err(user_error)?;
+    let write_error =
+        |err| user_error_with_message(format!("Failed to write {}", disk_path.display()), err);
+    if let Some(parent) = disk_path.parent() {
+        fs::create_dir_all(parent).map_err(write_error)?;
+    }
+    let file = File::create(&disk_path).map_err(write_error)?;
+    #[cfg(unix)]
+    if executable {
+        use std::os::unix::fs::PermissionsExt as _;
+        file.set_permissions(fs::Permissions::from_mode(0o755))
+            .map_err(write_error)?;
+    }
+    #[cfg(not(unix))]
+    let _ = executable;
+    Ok(Box::new(file))
+}
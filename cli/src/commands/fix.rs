@@ -215,7 +215,13 @@ pub(crate) fn cmd_fix(
         .block_on()
     });
 
-    print_unmatched_explicit_paths(ui, tx.base_workspace_helper(), &fileset_expression, &trees)?;
+    print_unmatched_explicit_paths(
+        ui,
+        tx.base_workspace_helper(),
+        &fileset_expression,
+        &trees,
+        &[],
+    )?;
 
     let summary = fix_files(
         commit_ids,
@@ -40,6 +40,7 @@ use crate::complete;
 use crate::git_util::load_git_import_options;
 use crate::git_util::print_git_import_stats;
 use crate::git_util::with_remote_git_callbacks;
+use crate::revset_util::parse_remote_fetch_bookmarks;
 use crate::revset_util::parse_union_name_patterns;
 use crate::ui::Ui;
 
@@ -51,6 +52,9 @@ use crate::ui::Ui;
 pub struct GitFetchArgs {
     /// Fetch only some of the branches
     ///
+    /// If not specified, the `remotes.<name>.fetch-bookmarks` setting of each
+    /// remote is used. If that isn't set, the remote's Git refspecs are used.
+    ///
     /// By default, the specified pattern matches branch names with glob syntax,
     /// but only `*` is expanded. Other wildcard characters such as `?` are
     /// *not* supported. Patterns can be repeated or combined with [logical
@@ -156,8 +160,14 @@ pub fn cmd_git_fetch(
             expansions.push((remote, expanded));
         }
     } else {
+        let remote_settings = tx.settings().remote_settings()?;
         let git_repo = get_git_backend(tx.repo_mut().store())?.git_repo();
         for remote in &matching_remotes {
+            if let Some(bookmark_expr) = parse_remote_fetch_bookmarks(ui, &remote_settings, remote)?
+            {
+                expansions.push((remote, expand_fetch_refspecs(remote, bookmark_expr)?));
+                continue;
+            }
             let (ignored, expanded) = expand_default_fetch_refspecs(remote, &git_repo)?;
             warn_ignored_refspecs(ui, remote, ignored)?;
             expansions.push((remote, expanded));
@@ -173,10 +183,22 @@ pub fn cmd_git_fetch(
         &import_options,
     )?;
 
-    for (remote, expanded) in expansions {
+    if expansions.len() > 1 {
+        // Transfers from different remotes are independent, so run them
+        // concurrently. Progress can't be displayed in that case.
+        let fetches = expansions
+            .into_iter()
+            .map(|(remote, expanded)| (*remote, expanded))
+            .collect();
         with_remote_git_callbacks(ui, |callbacks| {
-            git_fetch.fetch(remote, expanded, callbacks, None, None)
+            git_fetch.fetch_concurrently(fetches, callbacks, None, None)
         })?;
+    } else {
+        for (remote, expanded) in expansions {
+            with_remote_git_callbacks(ui, |callbacks| {
+                git_fetch.fetch(remote, expanded, callbacks, None, None)
+            })?;
+        }
     }
 
     let import_stats = git_fetch.import_refs()?;
@@ -69,6 +69,7 @@ use crate::formatter::Formatter;
 use crate::formatter::FormatterExt as _;
 use crate::git_util::with_remote_git_callbacks;
 use crate::revset_util::parse_bookmark_name;
+use crate::revset_util::parse_remote_push_bookmarks;
 use crate::revset_util::parse_union_name_patterns;
 use crate::ui::Ui;
 
@@ -1002,7 +1003,13 @@ fn find_bookmarks_targeted_by_revisions<'a>(
     use_default_revset: bool,
 ) -> Result<Vec<(&'a RefName, LocalAndRemoteRef<'a>)>, CommandError> {
     let mut revision_commit_ids = HashSet::new();
+    // Bookmarks selected by the default revset may be restricted by the
+    // `remotes.<name>.push-bookmarks` setting.
+    let mut default_commit_ids = HashSet::new();
+    let mut default_bookmarks_matcher = None;
     if use_default_revset {
+        let remote_settings = workspace_command.settings().remote_settings()?;
+        let bookmarks_expr = parse_remote_push_bookmarks(ui, &remote_settings, remote)?;
         // remote_bookmarks(remote=<remote>)..@
         let workspace_name = workspace_command.workspace_name();
         let expression = RevsetExpression::remote_bookmarks(
@@ -1011,7 +1018,10 @@ fn find_bookmarks_targeted_by_revisions<'a>(
             None,
         )
         .range(&RevsetExpression::working_copy(workspace_name.to_owned()))
-        .intersection(&RevsetExpression::bookmarks(StringExpression::all()));
+        .intersection(&RevsetExpression::bookmarks(
+            bookmarks_expr.clone().unwrap_or_else(StringExpression::all),
+        ));
+        default_bookmarks_matcher = bookmarks_expr.map(|expr| expr.to_matcher());
         let mut commit_ids = workspace_command
             .attach_revset_evaluator(expression)
             .evaluate_to_commit_ids()?
@@ -1025,7 +1035,7 @@ fn find_bookmarks_targeted_by_revisions<'a>(
             )?;
         }
         for commit_id in commit_ids {
-            revision_commit_ids.insert(commit_id?);
+            default_commit_ids.insert(commit_id?);
         }
     }
     for rev_arg in revisions {
@@ -1046,9 +1056,15 @@ fn find_bookmarks_targeted_by_revisions<'a>(
         .repo()
         .view()
         .local_remote_bookmarks(remote)
-        .filter(|(_, targets)| {
+        .filter(|(name, targets)| {
             let mut local_ids = targets.local_target.added_ids();
-            local_ids.any(|id| revision_commit_ids.contains(id))
+            local_ids.any(|id| {
+                revision_commit_ids.contains(id)
+                    || (default_commit_ids.contains(id)
+                        && default_bookmarks_matcher
+                            .as_ref()
+                            .is_none_or(|matcher| matcher.is_match(name.as_str())))
+            })
         })
         .collect_vec();
     Ok(bookmarks_targeted)
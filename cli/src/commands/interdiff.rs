@@ -106,6 +106,7 @@ pub(crate) fn cmd_interdiff(
             &to.parent_tree(repo.as_ref())?,
             &to.tree(),
         ],
+        &[],
     )?;
 
     let diff_renderer = workspace_command.diff_renderer_for(&args.format)?;
@@ -13,31 +13,41 @@
 // limitations under the License.
 
 use std::cmp::min;
+use std::collections::HashSet;
+use std::sync::Arc;
 
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
+use jj_lib::fileset::FilesetExpression;
 use jj_lib::graph::GraphEdge;
 use jj_lib::graph::GraphEdgeType;
 use jj_lib::graph::TopoGroupedGraphIterator;
 use jj_lib::graph::reverse_graph;
+use jj_lib::matchers::FilesMatcher;
 use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::revset::RevsetEvaluationError;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetFilterPredicate;
 use jj_lib::revset::RevsetIteratorExt as _;
+use jj_lib::revset::UserRevsetExpression;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::format_template;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
+use crate::diff_util::get_copy_records;
 use crate::formatter::FormatterExt as _;
 use crate::graphlog::GraphStyle;
 use crate::graphlog::get_graphlog;
@@ -81,6 +91,15 @@ pub(crate) struct LogArgs {
     #[arg(add = ArgValueCompleter::new(complete::log_files))]
     paths: Vec<String>,
 
+    /// Follow the given file paths across renames and copies
+    ///
+    /// Revisions modifying the paths the files were renamed or copied from are
+    /// also shown, up to the revision that renamed or copied them. Only the
+    /// renames detected by the backend are taken into account. Patterns other
+    /// than file paths are matched without following renames.
+    #[arg(long, requires = "paths")]
+    follow: bool,
+
     /// Limit number of revisions to show
     ///
     /// Applied after revisions are filtered and reordered topologically, but
@@ -138,6 +157,7 @@ pub(crate) fn cmd_log(
 
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
     let mut explicit_paths = fileset_expression.explicit_paths().collect_vec();
+    let mut followed_fileset_expression = None;
     let revset_expression = {
         // only use default revset if neither revset nor path are specified
         let mut expression = if args.revisions.is_empty() && args.paths.is_empty() {
@@ -149,7 +169,27 @@ pub(crate) fn cmd_log(
             // a path was specified so we use all() and add path filter later
             workspace_command.attach_revset_evaluator(RevsetExpression::all())
         };
-        if !args.paths.is_empty() {
+        if args.follow {
+            if explicit_paths.is_empty() {
+                return Err(user_error("--follow requires file paths to be specified"));
+            }
+            let (follow_expression, followed_paths) = follow_renames(
+                &workspace_command,
+                expression.expression(),
+                &explicit_paths,
+                args.limit,
+            )?;
+            // Non-explicit patterns are matched as usual, without following
+            // renames.
+            let predicate = RevsetFilterPredicate::File(fileset_expression.clone());
+            expression
+                .intersect_with(&RevsetExpression::filter(predicate).union(&follow_expression));
+            followed_fileset_expression = Some(FilesetExpression::union_all(
+                std::iter::once(fileset_expression.clone())
+                    .chain(followed_paths.into_iter().map(FilesetExpression::file_path))
+                    .collect(),
+            ));
+        } else if !args.paths.is_empty() {
             // Beware that args.paths = ["root:."] is not identical to []. The
             // former will filter out empty commits.
             let predicate = RevsetFilterPredicate::File(fileset_expression.clone());
@@ -182,7 +222,10 @@ pub(crate) fn cmd_log(
     let prio_revset = workspace_command.parse_revset(ui, &RevisionArg::from(prio_revset))?;
 
     let repo = workspace_command.repo();
-    let matcher = fileset_expression.to_matcher();
+    let matcher = followed_fileset_expression
+        .as_ref()
+        .unwrap_or(&fileset_expression)
+        .to_matcher();
 
     let store = repo.store();
     let diff_renderer = workspace_command.diff_renderer_for_log(&args.diff_format, args.patch)?;
@@ -385,3 +428,59 @@ pub(crate) fn cmd_log(
 
     Ok(())
 }
+
+/// Returns an expression selecting the revisions in `base_expression` that
+/// modify one of the `paths`, or one of the paths they were renamed or copied
+/// from in an ancestor revision. Also returns those source paths.
+///
+/// If `limit` is specified, renames are only looked up in the first `limit`
+/// revisions modifying each path, since older revisions wouldn't be shown.
+fn follow_renames(
+    workspace_command: &WorkspaceCommandHelper,
+    base_expression: &Arc<UserRevsetExpression>,
+    paths: &[&RepoPath],
+    limit: Option<usize>,
+) -> Result<(Arc<UserRevsetExpression>, Vec<RepoPathBuf>), CommandError> {
+    let store = workspace_command.repo().store();
+    let mut pending = paths
+        .iter()
+        .map(|&path| (path.to_owned(), base_expression.clone()))
+        .collect_vec();
+    let mut seen_copies = HashSet::new();
+    let mut source_paths = vec![];
+    let mut expressions = vec![];
+    while let Some((path, scope)) = pending.pop() {
+        let expression = scope.filtered(RevsetFilterPredicate::File(FilesetExpression::file_path(
+            path.clone(),
+        )));
+        let matcher = FilesMatcher::new([&path]);
+        let commits = workspace_command
+            .attach_revset_evaluator(expression.clone())
+            .evaluate_to_commits()?;
+        for commit in commits.take(limit.unwrap_or(usize::MAX)) {
+            let commit = commit?;
+            for parent in commit.parents() {
+                let parent = parent?;
+                // Renames and copies only add the target path.
+                if parent.tree().path_value(&path)?.is_present() {
+                    continue;
+                }
+                let parent_id = parent.id();
+                for record in get_copy_records(store, parent_id, commit.id(), &matcher)? {
+                    let source = record?.source;
+                    if source == path || !seen_copies.insert((source.clone(), commit.id().clone()))
+                    {
+                        continue;
+                    }
+                    // The source path is only followed in the history before
+                    // the copy, not in unrelated revisions reusing that path.
+                    let ancestors = RevsetExpression::commit(parent_id.clone()).ancestors();
+                    pending.push((source.clone(), scope.intersection(&ancestors)));
+                    source_paths.push(source);
+                }
+            }
+        }
+        expressions.push(expression);
+    }
+    Ok((RevsetExpression::union_all(&expressions), source_paths))
+}
@@ -14,6 +14,8 @@
 
 mod abandon;
 mod absorb;
+mod apply;
+mod archive;
 #[cfg(feature = "bench")]
 mod bench;
 mod bisect;
@@ -27,6 +29,7 @@ mod diffedit;
 mod duplicate;
 mod edit;
 mod evolog;
+mod export;
 mod file;
 mod fix;
 #[cfg(feature = "git")]
@@ -45,6 +48,7 @@ mod prev;
 mod rebase;
 mod redo;
 mod resolve;
+mod resolve_divergence;
 mod restore;
 mod revert;
 mod root;
@@ -93,6 +97,8 @@ const STYLES: Styles = Styles::styled()
 enum Command {
     Abandon(abandon::AbandonArgs),
     Absorb(absorb::AbsorbArgs),
+    Apply(apply::ApplyArgs),
+    Archive(archive::ArchiveArgs),
     #[cfg(feature = "bench")]
     #[command(subcommand)]
     Bench(bench::BenchCommand),
@@ -112,6 +118,7 @@ enum Command {
     Edit(edit::EditArgs),
     #[command(alias = "obslog", visible_alias = "evolution-log")]
     Evolog(evolog::EvologArgs),
+    Export(export::ExportArgs),
     #[command(subcommand)]
     File(file::FileCommand),
     Fix(fix::FixArgs),
@@ -135,6 +142,7 @@ enum Command {
     Rebase(rebase::RebaseArgs),
     Redo(redo::RedoArgs),
     Resolve(resolve::ResolveArgs),
+    ResolveDivergence(resolve_divergence::ResolveDivergenceArgs),
     Restore(restore::RestoreArgs),
     Revert(revert::RevertArgs),
     Root(root::RootArgs),
@@ -170,6 +178,8 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
     match &subcommand {
         Command::Abandon(args) => abandon::cmd_abandon(ui, command_helper, args),
         Command::Absorb(args) => absorb::cmd_absorb(ui, command_helper, args),
+        Command::Apply(args) => apply::cmd_apply(ui, command_helper, args),
+        Command::Archive(args) => archive::cmd_archive(ui, command_helper, args),
         #[cfg(feature = "bench")]
         Command::Bench(args) => bench::cmd_bench(ui, command_helper, args),
         Command::Bisect(args) => bisect::cmd_bisect(ui, command_helper, args),
@@ -183,6 +193,7 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Duplicate(args) => duplicate::cmd_duplicate(ui, command_helper, args),
         Command::Edit(args) => edit::cmd_edit(ui, command_helper, args),
         Command::Evolog(args) => evolog::cmd_evolog(ui, command_helper, args),
+        Command::Export(args) => export::cmd_export(ui, command_helper, args),
         Command::File(args) => file::cmd_file(ui, command_helper, args),
         Command::Fix(args) => fix::cmd_fix(ui, command_helper, args),
         #[cfg(feature = "git")]
@@ -201,6 +212,9 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Rebase(args) => rebase::cmd_rebase(ui, command_helper, args),
         Command::Redo(args) => redo::cmd_redo(ui, command_helper, args),
         Command::Resolve(args) => resolve::cmd_resolve(ui, command_helper, args),
+        Command::ResolveDivergence(args) => {
+            resolve_divergence::cmd_resolve_divergence(ui, command_helper, args)
+        }
         Command::Restore(args) => restore::cmd_restore(ui, command_helper, args),
         Command::Revert(args) => revert::cmd_revert(ui, command_helper, args),
         Command::Root(args) => root::cmd_root(ui, command_helper, args),
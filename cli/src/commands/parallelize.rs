@@ -13,13 +13,21 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::io::Write as _;
 
 use clap_complete::ArgValueCompleter;
+use futures::StreamExt as _;
+use indexmap::IndexMap;
 use indexmap::IndexSet;
 use itertools::Itertools as _;
+use jj_lib::backend::BackendResult;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::commit::CommitIteratorExt as _;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPathBuf;
+use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
@@ -53,6 +61,10 @@ use crate::ui::Ui;
 /// Therefore, `jj parallelize '1 | 3'` is a no-op. That's because 2, which is
 /// not in the target set, was a descendant of 1 before, so it remains a
 /// descendant, and it was an ancestor of 3 before, so it remains an ancestor.
+///
+/// A warning is printed if more than one of the revisions modifies the same
+/// path, since such revisions (and merges of them) are likely to conflict once
+/// they no longer build on each other.
 #[derive(clap::Args, Clone, Debug)]
 #[command(verbatim_doc_comment)]
 pub(crate) struct ParallelizeArgs {
@@ -95,6 +107,8 @@ pub(crate) fn cmd_parallelize(
     }
 
     workspace_command.check_rewritable(needs_rewrite)?;
+    let overlapping_paths =
+        find_overlapping_paths(workspace_command.repo().as_ref(), &target_commits)?;
     let mut tx = workspace_command.start_transaction();
 
     // If a commit outside the target set has a commit in the target set as parent,
@@ -143,5 +157,41 @@ pub(crate) fn cmd_parallelize(
         },
     )?;
 
+    if !overlapping_paths.is_empty() {
+        writeln!(
+            ui.warning_default(),
+            "The following paths are modified by more than one of the parallelized revisions:"
+        )?;
+        let mut formatter = ui.stderr_formatter();
+        for path in &overlapping_paths {
+            writeln!(
+                formatter,
+                "  {}",
+                tx.base_workspace_helper().format_file_path(path)
+            )?;
+        }
+    }
     tx.finish(ui, format!("parallelize {} commits", target_commits.len()))
 }
+
+/// Returns the paths modified by more than one of the given commits.
+fn find_overlapping_paths(repo: &dyn Repo, commits: &[Commit]) -> BackendResult<Vec<RepoPathBuf>> {
+    let mut num_modifying_commits: IndexMap<RepoPathBuf, usize> = IndexMap::new();
+    for commit in commits {
+        let parent_tree = commit.parent_tree(repo)?;
+        let paths: Vec<_> = parent_tree
+            .diff_stream(&commit.tree(), &EverythingMatcher)
+            .map(|entry| entry.path)
+            .collect()
+            .block_on();
+        for path in paths {
+            *num_modifying_commits.entry(path).or_default() += 1;
+        }
+    }
+    Ok(num_modifying_commits
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(path, _)| path)
+        .sorted()
+        .collect())
+}
@@ -24,11 +24,13 @@ use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo as _;
 use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetIteratorExt as _;
 use jj_lib::rewrite::EmptyBehavior;
 use jj_lib::rewrite::MoveCommitsLocation;
 use jj_lib::rewrite::MoveCommitsStats;
 use jj_lib::rewrite::MoveCommitsTarget;
 use jj_lib::rewrite::RebaseOptions;
+use jj_lib::rewrite::RebasedCommit;
 use jj_lib::rewrite::RewriteRefsOptions;
 use jj_lib::rewrite::compute_move_commits;
 use jj_lib::rewrite::find_duplicate_divergent_commits;
@@ -37,12 +39,14 @@ use tracing::instrument;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::WorkspaceCommandHelper;
+use crate::cli_util::WorkspaceCommandTransaction;
 use crate::cli_util::compute_commit_location;
 use crate::cli_util::print_updated_commits;
 use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
 use crate::complete;
+use crate::formatter::Formatter;
 use crate::ui::Ui;
 
 /// Move revisions to different parent(s)
@@ -317,6 +321,12 @@ pub(crate) struct RebaseArgs {
     /// destination with identical changes.
     #[arg(long)]
     keep_divergent: bool,
+
+    /// Only display the new parents of the rebased commits
+    ///
+    /// The rebase is computed as usual, but the repository is left unchanged.
+    #[arg(long)]
+    dry_run: bool,
 }
 
 #[derive(clap::Args, Clone, Debug)]
@@ -406,6 +416,11 @@ pub(crate) fn cmd_rebase(
         }
     };
     let stats = computed_move.apply(tx.repo_mut(), &rebase_options)?;
+    if args.dry_run {
+        print_rebase_plan(ui.stdout_formatter().as_mut(), &tx, &stats)?;
+        writeln!(ui.status(), "Dry-run requested, not rebasing.")?;
+        return Ok(());
+    }
     print_move_commits_stats(ui, &stats)?;
     tx.finish(ui, tx_description(&loc.target))?;
 
@@ -573,6 +588,56 @@ fn tx_description(target: &MoveCommitsTarget) -> String {
     }
 }
 
+/// Prints the new parents of the commits rebased by the given transaction.
+fn print_rebase_plan(
+    formatter: &mut dyn Formatter,
+    tx: &WorkspaceCommandTransaction,
+    stats: &MoveCommitsStats,
+) -> Result<(), CommandError> {
+    let new_commit_ids = stats
+        .rebased_commits
+        .values()
+        .filter_map(|rebased| match rebased {
+            RebasedCommit::Rewritten(commit) => Some(commit.id().clone()),
+            RebasedCommit::Abandoned { .. } => None,
+        })
+        .collect_vec();
+    let abandoned_commits: Vec<_> = stats
+        .rebased_commits
+        .iter()
+        .filter(|(_, rebased)| matches!(rebased, RebasedCommit::Abandoned { .. }))
+        .map(|(old_id, _)| tx.base_repo().store().get_commit(old_id))
+        .try_collect()?;
+    let template = tx.commit_summary_template();
+    if !new_commit_ids.is_empty() {
+        writeln!(formatter, "Rebase plan:")?;
+        let new_commits: Vec<_> = RevsetExpression::commits(new_commit_ids)
+            .evaluate(tx.repo())?
+            .iter()
+            .commits(tx.repo().store())
+            .try_collect()?;
+        for commit in &new_commits {
+            write!(formatter, "  ")?;
+            template.format(commit, formatter)?;
+            writeln!(formatter)?;
+            for parent in commit.parents() {
+                write!(formatter, "    onto ")?;
+                template.format(&parent?, formatter)?;
+                writeln!(formatter)?;
+            }
+        }
+    }
+    if !abandoned_commits.is_empty() {
+        writeln!(formatter, "Commits to abandon:")?;
+        print_updated_commits(
+            formatter,
+            &tx.base_workspace_helper().commit_summary_template(),
+            &abandoned_commits,
+        )?;
+    }
+    Ok(())
+}
+
 /// Print details about the provided [`MoveCommitsStats`].
 fn print_move_commits_stats(ui: &Ui, stats: &MoveCommitsStats) -> std::io::Result<()> {
     let Some(mut formatter) = ui.status_formatter() else {
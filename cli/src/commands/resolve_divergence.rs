@@ -0,0 +1,446 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::Write as _;
+use std::sync::Arc;
+
+use clap_complete::ArgValueCompleter;
+use indexmap::IndexMap;
+use itertools::Itertools as _;
+use jj_lib::backend::ChangeId;
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::index::ResolvedChangeTargets;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::ResolvedRevsetExpression;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetIteratorExt as _;
+use jj_lib::rewrite::CommitWithSelection;
+use jj_lib::rewrite::RebaseOptions;
+use jj_lib::rewrite::RebasedCommit;
+use jj_lib::rewrite::squash_commits;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::cli_util::print_updated_commits;
+use crate::cli_util::short_change_hash;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Resolve divergent changes
+///
+/// A change is divergent when more than one visible commit has its change ID.
+/// This command picks one of those commits to keep for each divergent change
+/// and either abandons the other commits, squashes their changes into the kept
+/// commit, or gives them new change IDs.
+///
+/// Descendants of abandoned or squashed commits are rebased onto the kept
+/// commit, and bookmarks pointing to them are moved to the kept commit.
+///
+/// If none of `--keep`, `--keep-newest`, or `--interactive` is given, the
+/// divergent changes are listed without modifying the repo.
+#[derive(clap::Args, Clone, Debug)]
+#[command(group(clap::ArgGroup::new("choice").args(&["keep", "keep_newest", "interactive"])))]
+pub(crate) struct ResolveDivergenceArgs {
+    /// Resolve divergent changes having commits in these revisions (default:
+    /// `mutable()`) [aliases: -r]
+    #[arg(value_name = "REVSETS")]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
+    revisions_pos: Vec<RevisionArg>,
+
+    #[arg(short = 'r', hide = true, value_name = "REVSETS")]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
+    revisions_opt: Vec<RevisionArg>,
+
+    /// The commits to keep
+    ///
+    /// Divergent changes without a commit in this set are left unchanged.
+    #[arg(long, value_name = "REVSETS")]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
+    keep: Vec<RevisionArg>,
+
+    /// Keep the commit with the most recent committer timestamp
+    #[arg(long)]
+    keep_newest: bool,
+
+    /// Choose the commit to keep for each divergent change interactively
+    #[arg(long, short)]
+    interactive: bool,
+
+    /// Squash the changes of the other commits into the kept commit instead of
+    /// abandoning them
+    ///
+    /// The description of the kept commit is preserved.
+    #[arg(long, requires = "choice", conflicts_with = "update_change_ids")]
+    merge: bool,
+
+    /// Give the other commits new change IDs instead of abandoning them
+    #[arg(long, requires = "choice")]
+    update_change_ids: bool,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_resolve_divergence(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ResolveDivergenceArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let target_expr = if !args.revisions_pos.is_empty() || !args.revisions_opt.is_empty() {
+        workspace_command
+            .parse_union_revsets(ui, &[&*args.revisions_pos, &*args.revisions_opt].concat())?
+    } else {
+        workspace_command.parse_revset(ui, &RevisionArg::from("mutable()".to_owned()))?
+    }
+    .resolve()?;
+    let divergent_changes = find_divergent_changes(&workspace_command, target_expr)?;
+    if divergent_changes.is_empty() {
+        writeln!(ui.status(), "No divergent changes found.")?;
+        return Ok(());
+    }
+
+    if args.keep.is_empty() && !args.keep_newest && !args.interactive {
+        let mut formatter = ui.stdout_formatter();
+        let template = workspace_command.commit_summary_template();
+        for (change_id, commits) in &divergent_changes {
+            writeln!(
+                formatter,
+                "Change {} has {} visible commits:",
+                short_change_hash(change_id),
+                commits.len()
+            )?;
+            print_updated_commits(formatter.as_mut(), &template, commits)?;
+        }
+        return Ok(());
+    }
+
+    let keep_set: HashSet<CommitId> = if args.keep.is_empty() {
+        HashSet::new()
+    } else {
+        workspace_command
+            .parse_union_revsets(ui, &args.keep)?
+            .evaluate_to_commit_ids()?
+            .try_collect()?
+    };
+    let mut resolutions: Vec<(Commit, Vec<Commit>)> = Vec::new();
+    for (change_id, commits) in divergent_changes {
+        let kept = if args.interactive {
+            choose_commit(ui, &workspace_command, &change_id, &commits)?.clone()
+        } else if args.keep_newest {
+            commits
+                .iter()
+                .max_by_key(|commit| commit.committer().timestamp.timestamp)
+                .unwrap()
+                .clone()
+        } else {
+            match commits
+                .iter()
+                .filter(|commit| keep_set.contains(commit.id()))
+                .collect_vec()
+                .as_slice()
+            {
+                [] => continue,
+                [commit] => (*commit).clone(),
+                [_, _, ..] => {
+                    return Err(user_error(format!(
+                        "More than one commit of divergent change {} was specified with --keep",
+                        short_change_hash(&change_id)
+                    )));
+                }
+            }
+        };
+        let others = commits
+            .into_iter()
+            .filter(|commit| commit.id() != kept.id())
+            .collect_vec();
+        resolutions.push((kept, others));
+    }
+    if resolutions.is_empty() {
+        writeln!(ui.status(), "No divergent changes to resolve.")?;
+        return Ok(());
+    }
+
+    let to_rewrite = resolutions
+        .iter()
+        .flat_map(|(_, others)| others.iter().map(|commit| commit.id().clone()))
+        .collect_vec();
+    if args.merge {
+        // The kept commits are rewritten as well
+        let kept_ids = resolutions.iter().map(|(kept, _)| kept.id());
+        workspace_command.check_rewritable(to_rewrite.iter().chain(kept_ids))?;
+    } else {
+        workspace_command.check_rewritable(&to_rewrite)?;
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    let mut updated_commits = Vec::new();
+    // Maps commits rewritten by this command to their final versions.
+    let mut rewritten_commits: HashMap<CommitId, Commit> = HashMap::new();
+    let mut rebased = RebasedDescendants::new(resolutions.iter().map(|(kept, _)| kept.id()));
+    if args.update_change_ids {
+        let to_rewrite_set: HashSet<_> = to_rewrite.iter().cloned().collect();
+        tx.repo_mut()
+            .transform_descendants(to_rewrite, async |rewriter| {
+                if to_rewrite_set.contains(rewriter.old_commit().id()) {
+                    let new_commit = rewriter.reparent().generate_new_change_id().write()?;
+                    updated_commits.push(new_commit);
+                } else if rewriter.parents_changed() {
+                    let old_id = rewriter.old_commit().id().clone();
+                    let new_commit = rewriter.reparent().write()?;
+                    rebased.record(&old_id, Some(new_commit.id()));
+                    rewritten_commits.insert(old_id, new_commit);
+                }
+                Ok(())
+            })?;
+    } else if args.merge {
+        for (kept, others) in &resolutions {
+            // Earlier resolutions may have rebased the commits of this one.
+            let current =
+                |commit: &Commit| rewritten_commits.get(commit.id()).unwrap_or(commit).clone();
+            let destination = current(kept);
+            let sources: Vec<_> = others
+                .iter()
+                .map(|commit| -> Result<_, CommandError> {
+                    let commit = current(commit);
+                    Ok(CommitWithSelection {
+                        selected_tree: commit.tree(),
+                        parent_tree: commit.parent_tree(tx.repo())?,
+                        commit,
+                    })
+                })
+                .try_collect()?;
+            let squashed = squash_commits(tx.repo_mut(), &sources, &destination, false)?
+                .expect("divergent commits are always squashed");
+            let new_kept = squashed.commit_builder.write()?;
+            // Move bookmarks and descendants of the squashed commits to the
+            // kept commit rather than to their parents.
+            for source in &sources {
+                if !tx
+                    .repo()
+                    .index()
+                    .is_ancestor(source.commit.id(), destination.id())?
+                {
+                    tx.repo_mut()
+                        .set_rewritten_commit(source.commit.id().clone(), new_kept.id().clone());
+                }
+            }
+            record_rewrite(&mut rewritten_commits, kept.id(), &new_kept);
+            rebased.record(destination.id(), Some(new_kept.id()));
+            tx.repo_mut().rebase_descendants_with_options(
+                &RebaseOptions::default(),
+                |old_commit, rebased_commit| match rebased_commit {
+                    RebasedCommit::Rewritten(new_commit) => {
+                        rebased.record(old_commit.id(), Some(new_commit.id()));
+                        record_rewrite(&mut rewritten_commits, old_commit.id(), &new_commit);
+                    }
+                    RebasedCommit::Abandoned { .. } => rebased.record(old_commit.id(), None),
+                },
+            )?;
+            updated_commits.extend(others.iter().cloned());
+        }
+    } else {
+        for (kept, others) in &resolutions {
+            for commit in others {
+                // Abandoning an ancestor of the kept commit must not rebase
+                // the kept commit onto itself.
+                if tx.repo().index().is_ancestor(commit.id(), kept.id())? {
+                    tx.repo_mut().record_abandoned_commit(commit);
+                } else {
+                    tx.repo_mut()
+                        .set_rewritten_commit(commit.id().clone(), kept.id().clone());
+                }
+                updated_commits.push(commit.clone());
+            }
+        }
+        tx.repo_mut().rebase_descendants_with_options(
+            &RebaseOptions::default(),
+            |old_commit, rebased_commit| match rebased_commit {
+                RebasedCommit::Rewritten(new_commit) => {
+                    rebased.record(old_commit.id(), Some(new_commit.id()));
+                    rewritten_commits.insert(old_commit.id().clone(), new_commit);
+                }
+                RebasedCommit::Abandoned { .. } => rebased.record(old_commit.id(), None),
+            },
+        )?;
+    }
+    let num_rebased = rebased.count;
+    let kept_commits = resolutions
+        .iter()
+        .map(|(kept, _)| rewritten_commits.get(kept.id()).unwrap_or(kept))
+        .collect_vec();
+
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(
+            formatter,
+            "Resolved {} divergent changes by keeping:",
+            resolutions.len()
+        )?;
+        print_updated_commits(
+            formatter.as_mut(),
+            &tx.commit_summary_template(),
+            kept_commits,
+        )?;
+        if args.update_change_ids {
+            writeln!(
+                formatter,
+                "Assigned new change IDs to {} commits:",
+                updated_commits.len()
+            )?;
+            print_updated_commits(
+                formatter.as_mut(),
+                &tx.commit_summary_template(),
+                &updated_commits,
+            )?;
+        } else {
+            if args.merge {
+                writeln!(
+                    formatter,
+                    "Squashed {} commits into the kept commits:",
+                    updated_commits.len()
+                )?;
+            } else {
+                writeln!(formatter, "Abandoned {} commits:", updated_commits.len())?;
+            }
+            print_updated_commits(
+                formatter.as_mut(),
+                &tx.base_workspace_helper().commit_summary_template(),
+                &updated_commits,
+            )?;
+        }
+        if num_rebased > 0 {
+            writeln!(formatter, "Rebased {num_rebased} descendant commits")?;
+        }
+    }
+    tx.finish(
+        ui,
+        format!("resolve {} divergent changes", resolutions.len()),
+    )?;
+    Ok(())
+}
+
+/// Counts the descendants rebased by this command. Kept commits aren't counted
+/// when they are rewritten, and neither are repeated rebases of a descendant.
+struct RebasedDescendants {
+    kept_ids: HashSet<CommitId>,
+    rebased_ids: HashSet<CommitId>,
+    count: usize,
+}
+
+impl RebasedDescendants {
+    fn new<'a>(kept_ids: impl IntoIterator<Item = &'a CommitId>) -> Self {
+        Self {
+            kept_ids: kept_ids.into_iter().cloned().collect(),
+            rebased_ids: HashSet::new(),
+            count: 0,
+        }
+    }
+
+    fn record(&mut self, old_id: &CommitId, new_id: Option<&CommitId>) {
+        let ids = if self.kept_ids.contains(old_id) {
+            &mut self.kept_ids
+        } else {
+            if !self.rebased_ids.contains(old_id) {
+                self.count += 1;
+            }
+            &mut self.rebased_ids
+        };
+        ids.extend(new_id.cloned());
+    }
+}
+
+/// Records that `old_id` was rewritten to `new_commit`, updating earlier
+/// records that pointed to `old_id`.
+fn record_rewrite(
+    rewritten_commits: &mut HashMap<CommitId, Commit>,
+    old_id: &CommitId,
+    new_commit: &Commit,
+) {
+    for commit in rewritten_commits.values_mut() {
+        if commit.id() == old_id {
+            *commit = new_commit.clone();
+        }
+    }
+    rewritten_commits.insert(old_id.clone(), new_commit.clone());
+}
+
+/// Finds changes that have more than one visible commit, at least one of which
+/// is in `target_expr`.
+fn find_divergent_changes(
+    workspace_command: &WorkspaceCommandHelper,
+    target_expr: Arc<ResolvedRevsetExpression>,
+) -> Result<IndexMap<ChangeId, Vec<Commit>>, CommandError> {
+    let repo = workspace_command.repo().as_ref();
+    let mut divergent_changes = IndexMap::new();
+    let mut seen_change_ids = HashSet::new();
+    for commit in target_expr.evaluate(repo)?.iter().commits(repo.store()) {
+        let commit = commit?;
+        if !seen_change_ids.insert(commit.change_id().clone()) {
+            continue;
+        }
+        let commit_ids = repo
+            .resolve_change_id(commit.change_id())?
+            .and_then(ResolvedChangeTargets::into_visible)
+            .unwrap_or_default();
+        if commit_ids.len() < 2 {
+            continue;
+        }
+        let commits: Vec<Commit> = RevsetExpression::commits(commit_ids)
+            .evaluate(repo)?
+            .iter()
+            .commits(repo.store())
+            .try_collect()?;
+        divergent_changes.insert(commit.change_id().clone(), commits);
+    }
+    Ok(divergent_changes)
+}
+
+fn choose_commit<'a>(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    change_id: &ChangeId,
+    commits: &'a [Commit],
+) -> Result<&'a Commit, CommandError> {
+    writeln!(
+        ui.stderr(),
+        "Change {} is divergent, choose the commit to keep:",
+        short_change_hash(change_id)
+    )?;
+    let mut formatter = ui.stderr_formatter();
+    let template = workspace_command.commit_summary_template();
+    let mut choices: Vec<String> = Default::default();
+    for (i, commit) in commits.iter().enumerate() {
+        write!(formatter, "{}: ", i + 1)?;
+        template.format(commit, formatter.as_mut())?;
+        writeln!(formatter)?;
+        choices.push(format!("{}", i + 1));
+    }
+    writeln!(formatter, "q: quit the prompt")?;
+    choices.push("q".to_string());
+    drop(formatter);
+
+    let index = ui.prompt_choice(
+        "enter the index of the commit you want to keep",
+        &choices,
+        None,
+    )?;
+    commits
+        .get(index)
+        .ok_or_else(|| user_error("No commit chosen to keep"))
+}
@@ -163,6 +163,7 @@ pub(crate) fn cmd_restore(
         &workspace_command,
         &fileset_expression,
         [&to_tree, &from_tree],
+        &[],
     )?;
 
     if new_tree.tree_ids() == to_commit.tree_ids() {
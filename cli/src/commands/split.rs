@@ -252,6 +252,7 @@ impl SplitArgs {
                 &target_commit.parent_tree(repo.as_ref())?,
                 &target_commit.tree(),
             ],
+            &[],
         )?;
 
         Ok(ResolvedSplitArgs {
@@ -324,6 +324,7 @@ pub(crate) fn cmd_squash(
         tx.base_workspace_helper(),
         &fileset_expression,
         source_commits.iter().map(|commit| &commit.selected_tree),
+        &[],
     )?;
 
     if let Some(squashed) = rewrite::squash_commits(
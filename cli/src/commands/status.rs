@@ -12,7 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::borrow::Cow;
+
+use futures::StreamExt as _;
+use futures::stream::BoxStream;
 use itertools::Itertools as _;
+use jj_lib::copies::CopiesTreeDiffEntry;
 use jj_lib::copies::CopyRecords;
 use jj_lib::merge::Diff;
 use jj_lib::merged_tree::MergedTree;
@@ -25,12 +30,15 @@ use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::print_conflicted_paths;
 use crate::cli_util::print_snapshot_stats;
 use crate::cli_util::print_unmatched_explicit_paths;
 use crate::command_error::CommandError;
 use crate::diff_util::DiffFormat;
+use crate::diff_util::diff_status;
 use crate::diff_util::get_copy_records;
+use crate::formatter::Formatter;
 use crate::formatter::FormatterExt as _;
 use crate::ui::Ui;
 
@@ -45,6 +53,14 @@ use crate::ui::Ui;
 ///
 /// * [Conflicted bookmarks]
 ///
+/// With `--short`, only the changed and untracked paths are printed, one per
+/// line, prefixed by a status letter: `M` (modified), `A` (added), `D`
+/// (deleted), `R` (renamed), `C` (copied), `U` (conflicted), or `?`
+/// (untracked). Renamed and copied paths are printed as `R old -> new`. Paths
+/// containing whitespace, quotes, backslashes, or control characters are
+/// quoted like C string literals. This format is stable and suitable for use
+/// in scripts and editor integrations.
+///
 /// [Conflicted bookmarks]:
 ///     https://docs.jj-vcs.dev/latest/bookmarks/#conflicts
 #[derive(clap::Args, Clone, Debug)]
@@ -52,6 +68,11 @@ pub(crate) struct StatusArgs {
     /// Restrict the status display to these paths
     #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
+
+    /// Only print the status letter and path of each changed or untracked
+    /// file
+    #[arg(long, short)]
+    short: bool,
 }
 
 #[instrument(skip_all)]
@@ -76,15 +97,65 @@ pub(crate) fn cmd_status(
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
     let formatter = formatter.as_mut();
+    let untracked_paths = snapshot_stats
+        .untracked_paths
+        .keys()
+        .filter(|path| matcher.matches(path))
+        .collect_vec();
+
+    if args.short {
+        let Some(wc_commit) = &maybe_wc_commit else {
+            return Ok(());
+        };
+        let parent_tree = wc_commit.parent_tree(repo.as_ref())?;
+        let tree = wc_commit.tree();
+        print_unmatched_explicit_paths(
+            ui,
+            &workspace_command,
+            &fileset_expression,
+            [&tree],
+            &untracked_paths,
+        )?;
+        let mut copy_records = CopyRecords::default();
+        for parent in wc_commit.parent_ids() {
+            let records = get_copy_records(repo.store(), parent, wc_commit.id(), &matcher)?;
+            copy_records.add_records(records)?;
+        }
+        let tree_diff = parent_tree.diff_stream_with_copies(&tree, &matcher, &copy_records);
+        print_short_status(formatter, &workspace_command, tree_diff).block_on()?;
+        for (path, _) in tree.conflicts_matching(&matcher) {
+            let ui_path = workspace_command.format_file_path(&path);
+            writeln!(
+                formatter.labeled("diff").labeled("conflict"),
+                "U {}",
+                quote_path(&ui_path)
+            )?;
+        }
+        for path in untracked_paths {
+            let ui_path = workspace_command.format_file_path(path);
+            writeln!(
+                formatter.labeled("diff").labeled("untracked"),
+                "? {}",
+                quote_path(&ui_path)
+            )?;
+        }
+        return Ok(());
+    }
 
     if let Some(wc_commit) = &maybe_wc_commit {
         let parent_tree = wc_commit.parent_tree(repo.as_ref())?;
         let tree = wc_commit.tree();
 
-        print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree])?;
+        print_unmatched_explicit_paths(
+            ui,
+            &workspace_command,
+            &fileset_expression,
+            [&tree],
+            &untracked_paths,
+        )?;
 
         let wc_has_changes = tree.tree_ids() != parent_tree.tree_ids();
-        let wc_has_untracked = !snapshot_stats.untracked_paths.is_empty();
+        let wc_has_untracked = !untracked_paths.is_empty();
         if !wc_has_changes && !wc_has_untracked {
             writeln!(formatter, "The working copy has no changes.")?;
         } else {
@@ -111,23 +182,19 @@ pub(crate) fn cmd_status(
 
             if wc_has_untracked {
                 writeln!(formatter, "Untracked paths:")?;
-                visit_collapsed_untracked_files(
-                    snapshot_stats.untracked_paths.keys(),
-                    tree.clone(),
-                    |path, is_dir| {
-                        let ui_path = workspace_command.path_converter().format_file_path(path);
-                        writeln!(
-                            formatter.labeled("diff").labeled("untracked"),
-                            "? {ui_path}{}",
-                            if is_dir {
-                                std::path::MAIN_SEPARATOR_STR
-                            } else {
-                                ""
-                            }
-                        )?;
-                        Ok(())
-                    },
-                )
+                visit_collapsed_untracked_files(untracked_paths, tree.clone(), |path, is_dir| {
+                    let ui_path = workspace_command.path_converter().format_file_path(path);
+                    writeln!(
+                        formatter.labeled("diff").labeled("untracked"),
+                        "? {ui_path}{}",
+                        if is_dir {
+                            std::path::MAIN_SEPARATOR_STR
+                        } else {
+                            ""
+                        }
+                    )?;
+                    Ok(())
+                })
                 .block_on()?;
             }
         }
@@ -230,6 +297,72 @@ pub(crate) fn cmd_status(
     Ok(())
 }
 
+/// Prints the status letter and path of each changed file in the `--short`
+/// format. Conflicted files are skipped.
+async fn print_short_status(
+    formatter: &mut dyn Formatter,
+    workspace_command: &WorkspaceCommandHelper,
+    mut tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
+) -> Result<(), CommandError> {
+    let format_path =
+        |path: &RepoPath| quote_path(&workspace_command.format_file_path(path)).into_owned();
+    while let Some(CopiesTreeDiffEntry { path, values }) = tree_diff.next().await {
+        let values = values?;
+        if !values.after.is_resolved() {
+            continue;
+        }
+        let status = diff_status(&path, &values);
+        let ui_path = match path.to_diff() {
+            Some(paths) => format!(
+                "{} -> {}",
+                format_path(paths.before),
+                format_path(paths.after)
+            ),
+            None => format_path(path.target()),
+        };
+        writeln!(
+            formatter.labeled("diff").labeled(status.label()),
+            "{} {ui_path}",
+            status.char()
+        )?;
+    }
+    Ok(())
+}
+
+/// Quotes the path like a C string literal if it contains whitespace, quotes,
+/// backslashes, or control characters.
+fn quote_path(path: &str) -> Cow<'_, str> {
+    let needs_quoting = |c: char| {
+        c.is_whitespace()
+            || c.is_control()
+            || c == '"'
+            // Backslash is the path separator on Windows
+            || (c == '\\' && std::path::MAIN_SEPARATOR != '\\')
+    };
+    if !path.contains(needs_quoting) {
+        return Cow::Borrowed(path);
+    }
+    let mut quoted = String::from('"');
+    for c in path.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {
+                for b in c.to_string().bytes() {
+                    quoted.push_str(&format!("\\{b:03o}"));
+                }
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    Cow::Owned(quoted)
+}
+
 async fn visit_collapsed_untracked_files(
     untracked_paths: impl IntoIterator<Item = impl AsRef<RepoPath>>,
     tree: MergedTree,
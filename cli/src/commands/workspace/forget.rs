@@ -12,26 +12,52 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fs;
+
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
 use jj_lib::ref_name::WorkspaceNameBuf;
+use jj_lib::repo::Repo as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::command_error::user_error_with_message;
 use crate::complete;
 use crate::ui::Ui;
 
 /// Stop tracking a workspace's working-copy commit in the repo
 ///
-/// The workspace will not be touched on disk. It can be deleted from disk
-/// before or after running this command.
+/// The workspace will not be touched on disk unless `--delete` is passed. It
+/// can be deleted from disk before or after running this command.
+///
+/// Working-copy commits that are empty and have no description are abandoned.
+/// Pass `--abandon` to abandon them even if they have changes.
 #[derive(clap::Args, Clone, Debug)]
 pub struct WorkspaceForgetArgs {
     /// Names of the workspaces to forget. By default, forgets only the current
     /// workspace.
     #[arg(add = ArgValueCandidates::new(complete::workspaces))]
     workspaces: Vec<WorkspaceNameBuf>,
+
+    /// Abandon the working-copy commits of the forgotten workspaces
+    #[arg(long)]
+    abandon: bool,
+
+    /// Delete the directory of the current workspace from disk
+    ///
+    /// This is only allowed when forgetting the current workspace, and not
+    /// for the workspace that contains the repo. Since ignored and untracked
+    /// files are deleted too, you'll be asked to confirm unless `--force` is
+    /// passed.
+    #[arg(long)]
+    delete: bool,
+
+    /// Delete the workspace directory without asking for confirmation
+    #[arg(long, requires = "delete")]
+    force: bool,
 }
 
 #[instrument(skip_all)]
@@ -70,12 +96,82 @@ pub fn cmd_workspace_forget(
         return Ok(());
     }
 
+    let current_name = workspace_command.workspace_name().to_owned();
+    let workspace_root = workspace_command.workspace_root().to_owned();
+    if args.delete {
+        if !forget_ws.contains(&&current_name) {
+            return Err(user_error_with_hint(
+                "--delete can only be used when forgetting the current workspace",
+                "Run the command from the workspace to delete.",
+            ));
+        }
+        if workspace_command
+            .workspace()
+            .repo_path()
+            .starts_with(&workspace_root)
+        {
+            return Err(user_error(
+                "Cannot delete the workspace that contains the repo",
+            ));
+        }
+        if !args.force {
+            let prompt = format!(
+                "Delete {} and everything in it, including ignored and untracked files?",
+                workspace_root.display()
+            );
+            if !ui.prompt_yes_no(&prompt, Some(false))? {
+                return Err(user_error_with_hint(
+                    "Workspace was not forgotten",
+                    "Pass --force to delete the workspace directory without confirmation.",
+                ));
+            }
+        }
+    }
+    let abandoned_commits: Vec<_> = if args.abandon {
+        let repo = workspace_command.repo();
+        let commit_ids = forget_ws
+            .iter()
+            .map(|ws| repo.view().get_wc_commit_id(ws).unwrap())
+            .filter(|&id| id != repo.store().root_commit_id())
+            // Commits still used by other workspaces are kept
+            .filter(|&id| {
+                repo.view()
+                    .wc_commit_ids()
+                    .iter()
+                    .all(|(name, wc_id)| wc_id != id || forget_ws.contains(&name))
+            })
+            .unique()
+            .cloned()
+            .collect_vec();
+        workspace_command.check_rewritable(&commit_ids)?;
+        commit_ids
+            .iter()
+            .map(|id| repo.store().get_commit(id))
+            .try_collect()?
+    } else {
+        vec![]
+    };
+
     // bundle every workspace forget into a single transaction, so that e.g.
     // undo correctly restores all of them at once.
     let mut tx = workspace_command.start_transaction();
+    for commit in &abandoned_commits {
+        tx.repo_mut().record_abandoned_commit(commit);
+    }
     forget_ws
         .iter()
         .try_for_each(|ws| tx.repo_mut().remove_wc_commit(ws))?;
+    let num_rebased = tx.repo_mut().rebase_descendants()?;
+    if !abandoned_commits.is_empty() {
+        writeln!(
+            ui.status(),
+            "Abandoned {} working-copy commits",
+            abandoned_commits.len()
+        )?;
+    }
+    if num_rebased > 0 {
+        writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
+    }
     let description = if let [ws] = forget_ws.as_slice() {
         format!("forget workspace {}", ws.as_symbol())
     } else {
@@ -86,5 +182,19 @@ pub fn cmd_workspace_forget(
     };
 
     tx.finish(ui, description)?;
+
+    if args.delete {
+        fs::remove_dir_all(&workspace_root).map_err(|err| {
+            user_error_with_message(
+                format!("Failed to delete {}", workspace_root.display()),
+                err,
+            )
+        })?;
+        writeln!(
+            ui.status(),
+            "Deleted workspace directory {}",
+            workspace_root.display()
+        )?;
+    }
     Ok(())
 }
@@ -15,6 +15,7 @@
 //! Template environment for `jj log`, `jj evolog` and similar.
 
 use std::any::Any;
+use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::cmp::max;
 use std::collections::HashMap;
@@ -29,6 +30,7 @@ use futures::StreamExt as _;
 use futures::TryStreamExt as _;
 use futures::stream::BoxStream;
 use itertools::Itertools as _;
+use jj_lib::backend::BackendError;
 use jj_lib::backend::BackendResult;
 use jj_lib::backend::ChangeId;
 use jj_lib::backend::CommitId;
@@ -960,6 +962,7 @@ pub struct CommitKeywordCache<'repo> {
     tags_index: OnceCell<Rc<CommitRefsIndex>>,
     git_refs_index: OnceCell<Rc<CommitRefsIndex>>,
     is_immutable_fn: OnceCell<Rc<RevsetContainingFn<'repo>>>,
+    diff_stats: Rc<RefCell<DiffStatsCache>>,
 }
 
 impl<'repo> CommitKeywordCache<'repo> {
@@ -978,6 +981,11 @@ impl<'repo> CommitKeywordCache<'repo> {
             .get_or_init(|| Rc::new(build_commit_refs_index(repo.view().git_refs())))
     }
 
+    /// Diff stats of the current commit, shared by all `diff().stat()` call sites.
+    pub fn diff_stats(&self) -> &Rc<RefCell<DiffStatsCache>> {
+        &self.diff_stats
+    }
+
     pub fn is_immutable_fn(
         &self,
         language: &CommitTemplateLanguage<'repo>,
@@ -1291,10 +1299,13 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
                 // https://github.com/jj-vcs/jj/issues/2933#issuecomment-1925870731
                 FilesetExpression::all()
             };
+            let files_text = files_node.map_or("", |node| node.span.as_str()).to_owned();
             let repo = language.repo;
             let matcher: Rc<dyn Matcher> = files.to_matcher().into();
-            let out_property = self_property
-                .and_then(move |commit| Ok(TreeDiff::from_commit(repo, &commit, matcher.clone())?));
+            let out_property = self_property.and_then(move |commit| {
+                let diff = TreeDiff::from_commit(repo, &commit, matcher.clone())?;
+                Ok(diff.with_cache_key((commit.id().clone(), files_text.clone())))
+            });
             Ok(out_property.into_dyn_wrapped())
         },
     );
@@ -1477,6 +1488,7 @@ fn builtin_commit_evolution_entry_methods<'repo>()
                     to_tree,
                     matcher: matcher.clone(),
                     copy_records: CopyRecords::default(), // TODO: copy tracking
+                    cache_key: None,
                 })
             });
             Ok(out_property.into_dyn_wrapped())
@@ -2234,6 +2246,38 @@ pub struct TreeDiff {
     to_tree: MergedTree,
     matcher: Rc<dyn Matcher>,
     copy_records: CopyRecords,
+    cache_key: Option<DiffCacheKey>,
+}
+
+/// Commit and fileset text identifying a commit diff.
+type DiffCacheKey = (CommitId, String);
+
+/// Cache of diff stats computed for the commit being evaluated.
+///
+/// Entries of the previous commit are dropped when stats of another commit are
+/// inserted, so the cache doesn't grow over a long `jj log` run.
+#[derive(Debug, Default)]
+pub struct DiffStatsCache {
+    commit_id: Option<CommitId>,
+    stats_by_files: HashMap<String, DiffStats>,
+}
+
+impl DiffStatsCache {
+    fn get(&self, (commit_id, files_text): &DiffCacheKey) -> Option<&DiffStats> {
+        if self.commit_id.as_ref() == Some(commit_id) {
+            self.stats_by_files.get(files_text)
+        } else {
+            None
+        }
+    }
+
+    fn insert(&mut self, (commit_id, files_text): DiffCacheKey, stats: DiffStats) {
+        if self.commit_id.as_ref() != Some(&commit_id) {
+            self.commit_id = Some(commit_id);
+            self.stats_by_files.clear();
+        }
+        self.stats_by_files.insert(files_text, stats);
+    }
 }
 
 impl TreeDiff {
@@ -2253,9 +2297,17 @@ impl TreeDiff {
             to_tree: commit.tree(),
             matcher,
             copy_records,
+            cache_key: None,
         })
     }
 
+    fn with_cache_key(self, cache_key: DiffCacheKey) -> Self {
+        Self {
+            cache_key: Some(cache_key),
+            ..self
+        }
+    }
+
     fn diff_stream(&self) -> BoxStream<'_, CopiesTreeDiffEntry> {
         self.from_tree
             .diff_stream_with_copies(&self.to_tree, &*self.matcher, &self.copy_records)
@@ -2427,12 +2479,25 @@ fn builtin_tree_diff_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, T
             // No user configuration exists for diff stat.
             let options = diff_util::DiffStatOptions::default();
             let conflict_marker_style = language.conflict_marker_style;
-            // TODO: cache and reuse stats within the current evaluation?
+            let cache = language.keyword_cache.diff_stats().clone();
             let out_property = (self_property, width_property).and_then(move |(diff, width)| {
-                let store = diff.from_tree.store();
-                let tree_diff = diff.diff_stream();
-                let stats = DiffStats::calculate(store, tree_diff, &options, conflict_marker_style)
-                    .block_on()?;
+                let cached = diff
+                    .cache_key
+                    .as_ref()
+                    .and_then(|key| cache.borrow().get(key).cloned());
+                let stats = if let Some(stats) = cached {
+                    stats
+                } else {
+                    let store = diff.from_tree.store();
+                    let tree_diff = diff.diff_stream();
+                    let stats =
+                        DiffStats::calculate(store, tree_diff, &options, conflict_marker_style)
+                            .block_on()?;
+                    if let Some(key) = diff.cache_key.clone() {
+                        cache.borrow_mut().insert(key, stats.clone());
+                    }
+                    stats
+                };
                 Ok(DiffStatsFormatted {
                     stats,
                     path_converter,
@@ -2619,9 +2684,42 @@ fn builtin_tree_entry_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo,
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "size",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let store = language.repo.store().clone();
+            let out_property = self_property.and_then(move |entry| {
+                let size = get_content_size(&store, &entry).block_on()?;
+                Ok(i64::try_from(size)?)
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map
 }
 
+/// Returns the size of the file content or the symlink target of the entry.
+async fn get_content_size(store: &Store, entry: &TreeEntry) -> BackendResult<u64> {
+    match entry.value.as_resolved() {
+        Some(Some(TreeValue::File { id, .. })) => {
+            let mut reader = store.read_file(&entry.path, id).await?;
+            tokio::io::copy(&mut reader, &mut tokio::io::sink())
+                .await
+                .map_err(|err| BackendError::ReadFile {
+                    path: entry.path.clone(),
+                    id: id.clone(),
+                    source: err.into(),
+                })
+        }
+        Some(Some(TreeValue::Symlink(id))) => {
+            let target = store.read_symlink(&entry.path, id).await?;
+            Ok(target.len() as u64)
+        }
+        _ => Ok(0),
+    }
+}
+
 fn describe_file_type(value: &MergedTreeValue) -> &'static str {
     match value.as_resolved() {
         Some(Some(TreeValue::File { .. })) => "file",
@@ -3085,6 +3183,39 @@ mod tests {
         UserSettings::from_config(config).unwrap()
     }
 
+    #[test]
+    fn test_diff_stats_cache() {
+        let env = CommitTemplateTestEnv::init();
+        let store = env.test_workspace.repo.store();
+        let stats = DiffStats::calculate(
+            store,
+            futures::stream::empty().boxed(),
+            &diff_util::DiffStatOptions::default(),
+            ConflictMarkerStyle::Diff,
+        )
+        .block_on()
+        .unwrap();
+        let id1 = CommitId::from_hex("0001");
+        let id2 = CommitId::from_hex("0002");
+        let key = |id: &CommitId, files: &str| (id.clone(), files.to_owned());
+
+        let mut cache = DiffStatsCache::default();
+        assert!(cache.get(&key(&id1, "")).is_none());
+        cache.insert(key(&id1, ""), stats.clone());
+        cache.insert(key(&id1, "file"), stats.clone());
+        assert!(cache.get(&key(&id1, "")).is_some());
+        assert!(cache.get(&key(&id1, "file")).is_some());
+        assert!(cache.get(&key(&id1, "other")).is_none());
+        assert!(cache.get(&key(&id2, "")).is_none());
+
+        // Stats of the previous commit are dropped
+        cache.insert(key(&id2, ""), stats);
+        assert!(cache.get(&key(&id2, "")).is_some());
+        assert!(cache.get(&key(&id1, "")).is_none());
+        assert!(cache.get(&key(&id1, "file")).is_none());
+        assert_eq!(cache.stats_by_files.len(), 1);
+    }
+
     #[test]
     fn test_ref_symbol_type() {
         let mut env = CommitTemplateTestEnv::init();
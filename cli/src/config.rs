@@ -35,6 +35,7 @@ use jj_lib::config::ConfigResolutionContext;
 use jj_lib::config::ConfigSource;
 use jj_lib::config::ConfigValue;
 use jj_lib::config::StackedConfig;
+use jj_lib::file_util::expand_home_path;
 use regex::Captures;
 use regex::Regex;
 use serde::Serialize as _;
@@ -821,12 +822,15 @@ impl CommandNameAndArgs {
 
     /// Returns process builder configured with this after interpolating
     /// variables into the arguments.
+    ///
+    /// A leading `~/` in the command name is expanded to the home directory.
     pub fn to_command_with_variables<V: AsRef<str>>(
         &self,
         variables: &HashMap<&str, V>,
     ) -> Command {
         let (name, args) = self.split_name_and_args();
-        let mut cmd = Command::new(interpolate_variables_single(name.as_ref(), variables));
+        let name = interpolate_variables_single(name.as_ref(), variables);
+        let mut cmd = Command::new(expand_home_path(&name));
         if let Self::Structured { env, .. } = self {
             cmd.envs(env);
         }
@@ -101,7 +101,7 @@ use crate::ui::Ui;
 
 #[derive(clap::Args, Clone, Debug)]
 #[command(next_help_heading = "Diff Formatting Options")]
-#[command(group(clap::ArgGroup::new("short-format").args(&["summary", "stat", "types", "name_only"])))]
+#[command(group(clap::ArgGroup::new("short-format").args(&["summary", "stat", "numstat", "types", "name_only"])))]
 #[command(group(clap::ArgGroup::new("long-format").args(&["git", "color_words"])))]
 pub struct DiffFormatArgs {
     /// For each path, show only whether it was modified, added, or deleted
@@ -112,6 +112,13 @@ pub struct DiffFormatArgs {
     #[arg(long)]
     pub stat: bool,
 
+    /// For each path, show the number of added and removed lines
+    ///
+    /// The counts and the path are separated by tabs. Binary files are shown
+    /// with `-` in place of the counts.
+    #[arg(long)]
+    pub numstat: bool,
+
     /// For each path, show only its type before and after
     ///
     /// The diff is shown as two letters. The first letter indicates the type
@@ -164,6 +171,7 @@ pub enum DiffFormat {
     // Non-trivial parameters are boxed in order to keep the variants small
     Summary,
     Stat(Box<DiffStatOptions>),
+    NumStat(Box<DiffStatOptions>),
     Types,
     NameOnly,
     Git(Box<UnifiedDiffOptions>),
@@ -175,6 +183,7 @@ pub enum DiffFormat {
 enum BuiltinFormatKind {
     Summary,
     Stat,
+    NumStat,
     Types,
     NameOnly,
     Git,
@@ -188,6 +197,7 @@ impl BuiltinFormatKind {
     const ALL_VARIANTS: &[Self] = &[
         Self::Summary,
         Self::Stat,
+        Self::NumStat,
         Self::Types,
         Self::NameOnly,
         Self::Git,
@@ -198,6 +208,7 @@ impl BuiltinFormatKind {
         match name {
             "summary" => Ok(Self::Summary),
             "stat" => Ok(Self::Stat),
+            "numstat" => Ok(Self::NumStat),
             "types" => Ok(Self::Types),
             "name-only" => Ok(Self::NameOnly),
             "git" => Ok(Self::Git),
@@ -211,6 +222,8 @@ impl BuiltinFormatKind {
             Some(Self::Summary)
         } else if args.stat {
             Some(Self::Stat)
+        } else if args.numstat {
+            Some(Self::NumStat)
         } else if args.types {
             Some(Self::Types)
         } else if args.name_only {
@@ -232,7 +245,7 @@ impl BuiltinFormatKind {
 
     fn is_short(self) -> bool {
         match self {
-            Self::Summary | Self::Stat | Self::Types | Self::NameOnly => true,
+            Self::Summary | Self::Stat | Self::NumStat | Self::Types | Self::NameOnly => true,
             Self::Git | Self::ColorWords => false,
         }
     }
@@ -241,6 +254,7 @@ impl BuiltinFormatKind {
         match self {
             Self::Summary => "summary",
             Self::Stat => "stat",
+            Self::NumStat => "numstat",
             Self::Types => "types",
             Self::NameOnly => "name-only",
             Self::Git => "git",
@@ -260,6 +274,11 @@ impl BuiltinFormatKind {
                 options.merge_args(args);
                 Ok(DiffFormat::Stat(Box::new(options)))
             }
+            Self::NumStat => {
+                let mut options = DiffStatOptions::default();
+                options.merge_args(args);
+                Ok(DiffFormat::NumStat(Box::new(options)))
+            }
             Self::Types => Ok(DiffFormat::Types),
             Self::NameOnly => Ok(DiffFormat::NameOnly),
             Self::Git => {
@@ -491,6 +510,13 @@ impl<'a> DiffRenderer<'a> {
                             .block_on()?;
                     show_diff_stats(formatter, &stats, path_converter, width)?;
                 }
+                DiffFormat::NumStat(options) => {
+                    let tree_diff = diff_stream();
+                    let stats =
+                        DiffStats::calculate(store, tree_diff, options, self.conflict_marker_style)
+                            .block_on()?;
+                    show_diff_numstats(formatter, &stats, path_converter)?;
+                }
                 DiffFormat::Types => {
                     let tree_diff = diff_stream();
                     show_types(formatter, tree_diff, path_converter).await?;
@@ -581,6 +607,7 @@ impl<'a> DiffRenderer<'a> {
                 // wouldn't be useful.
                 DiffFormat::Summary
                 | DiffFormat::Stat(_)
+                | DiffFormat::NumStat(_)
                 | DiffFormat::Types
                 | DiffFormat::NameOnly => {}
                 DiffFormat::Git(options) => {
@@ -2197,6 +2224,25 @@ fn diff_summary_char(value: &MergedTreeValue) -> char {
     }
 }
 
+pub fn show_diff_numstats(
+    formatter: &mut dyn Formatter,
+    stats: &DiffStats,
+    path_converter: &RepoPathUiConverter,
+) -> io::Result<()> {
+    for stat in stats.entries() {
+        let ui_path = match stat.path.to_diff() {
+            Some(paths) => path_converter.format_copied_path(paths),
+            None => path_converter.format_file_path(stat.path.target()),
+        };
+        if let Some((added, removed)) = stat.added_removed {
+            writeln!(formatter, "{added}\t{removed}\t{ui_path}")?;
+        } else {
+            writeln!(formatter, "-\t-\t{ui_path}")?;
+        }
+    }
+    Ok(())
+}
+
 pub async fn show_names(
     formatter: &mut dyn Formatter,
     mut tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
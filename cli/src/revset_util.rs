@@ -27,6 +27,7 @@ use jj_lib::config::ConfigSource;
 use jj_lib::config::StackedConfig;
 use jj_lib::id_prefix::IdPrefixContext;
 use jj_lib::ref_name::RefNameBuf;
+use jj_lib::ref_name::RemoteName;
 use jj_lib::ref_name::RemoteNameBuf;
 use jj_lib::repo::Repo;
 use jj_lib::revset;
@@ -387,25 +388,65 @@ pub fn parse_remote_auto_track_bookmarks_map(
         let Some(text) = &settings.auto_track_bookmarks else {
             continue;
         };
-        let mut diagnostics = RevsetDiagnostics::new();
-        let expr = revset::parse_string_expression(&mut diagnostics, text).map_err(|err| {
-            // From<RevsetParseError>, but with different message and error kind
-            let hint = revset_parse_error_hint(&err);
-            let message = format!(
-                "Invalid `remotes.{}.auto-track-bookmarks`: {}",
-                name.as_symbol(),
-                err.kind()
-            );
-            let mut cmd_err = config_error_with_message(message, err);
-            cmd_err.extend_hints(hint);
-            cmd_err
-        })?;
-        print_parse_diagnostics(
-            ui,
-            &format!("In `remotes.{}.auto-track-bookmarks`", name.as_symbol()),
-            &diagnostics,
-        )?;
+        let expr = parse_remote_string_expression(ui, name, "auto-track-bookmarks", text)?;
         matchers.insert(name.clone(), expr.to_matcher());
     }
     Ok(matchers)
 }
+
+/// Parses the `remotes.<name>.fetch-bookmarks` setting of the given remote.
+pub fn parse_remote_fetch_bookmarks(
+    ui: &Ui,
+    remote_settings: &RemoteSettingsMap,
+    name: &RemoteName,
+) -> Result<Option<StringExpression>, CommandError> {
+    let Some(text) = remote_settings
+        .get(name)
+        .and_then(|settings| settings.fetch_bookmarks.as_ref())
+    else {
+        return Ok(None);
+    };
+    parse_remote_string_expression(ui, name, "fetch-bookmarks", text).map(Some)
+}
+
+/// Parses the `remotes.<name>.push-bookmarks` setting of the given remote.
+pub fn parse_remote_push_bookmarks(
+    ui: &Ui,
+    remote_settings: &RemoteSettingsMap,
+    name: &RemoteName,
+) -> Result<Option<StringExpression>, CommandError> {
+    let Some(text) = remote_settings
+        .get(name)
+        .and_then(|settings| settings.push_bookmarks.as_ref())
+    else {
+        return Ok(None);
+    };
+    parse_remote_string_expression(ui, name, "push-bookmarks", text).map(Some)
+}
+
+fn parse_remote_string_expression(
+    ui: &Ui,
+    name: &RemoteName,
+    key: &str,
+    text: &str,
+) -> Result<StringExpression, CommandError> {
+    let mut diagnostics = RevsetDiagnostics::new();
+    let expr = revset::parse_string_expression(&mut diagnostics, text).map_err(|err| {
+        // From<RevsetParseError>, but with different message and error kind
+        let hint = revset_parse_error_hint(&err);
+        let message = format!(
+            "Invalid `remotes.{name}.{key}`: {kind}",
+            name = name.as_symbol(),
+            kind = err.kind()
+        );
+        let mut cmd_err = config_error_with_message(message, err);
+        cmd_err.extend_hints(hint);
+        cmd_err
+    })?;
+    print_parse_diagnostics(
+        ui,
+        &format!("In `remotes.{name}.{key}`", name = name.as_symbol()),
+        &diagnostics,
+    )?;
+    Ok(expr)
+}
@@ -13,6 +13,8 @@ mod test_absorb_command;
 mod test_acls;
 mod test_advance_bookmarks;
 mod test_alias;
+mod test_apply_command;
+mod test_archive_command;
 mod test_bisect_command;
 mod test_bookmark_command;
 mod test_builtin_aliases;
@@ -31,6 +33,7 @@ mod test_diffedit_command;
 mod test_duplicate_command;
 mod test_edit_command;
 mod test_evolog_command;
+mod test_export_command;
 mod test_file_annotate_command;
 mod test_file_chmod_command;
 mod test_file_list_command;
@@ -66,6 +69,7 @@ mod test_parallelize_command;
 mod test_rebase_command;
 mod test_repo_change_report;
 mod test_resolve_command;
+mod test_resolve_divergence_command;
 mod test_restore_command;
 mod test_revert_command;
 mod test_revset_output;
@@ -48,6 +48,7 @@ fn test_basics() {
     ------- stderr -------
     Abandoned 1 commits:
       vruxwmqv 636920e4 d | d
+    Moved bookmarks to parents of abandoned commits: d
     Rebased 1 descendant commits onto parents of abandoned commits
     Working copy  (@) now at: znkkpsqq 38e96a1f e | e
     Parent commit (@-)      : rlvkpnrz 7d980be7 a | a
@@ -73,6 +74,7 @@ fn test_basics() {
     ------- stderr -------
     Abandoned 1 commits:
       znkkpsqq 03e0d4bf e | e
+    Moved bookmarks to parents of abandoned commits: e
     Working copy  (@) now at: nkmrtpmo 179731fc (empty) (no description set)
     Parent commit (@-)      : rlvkpnrz 7d980be7 a e?? | a
     Parent commit (@-)      : vruxwmqv 636920e4 d e?? | d
@@ -172,6 +174,34 @@ fn test_basics() {
     No revisions to abandon.
     [EOF]
     ");
+
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    let output = work_dir.run_jj(["abandon", "--config=abandon.retain-bookmarks=true", "d"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Abandoned 1 commits:
+      vruxwmqv 636920e4 d | d
+    Moved bookmarks to parents of abandoned commits: d
+    Rebased 1 descendant commits onto parents of abandoned commits
+    Working copy  (@) now at: znkkpsqq 19943f73 e | e
+    Parent commit (@-)      : rlvkpnrz 7d980be7 a | a
+    Parent commit (@-)      : royxmykx c12952d9 c d | c
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "abandon",
+        "--config=abandon.retain-bookmarks=true",
+        "--delete-bookmarks",
+        "b",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Abandoned 1 commits:
+      zsuskuln 123b4d91 b | b
+    Deleted bookmarks: b
+    [EOF]
+    ");
 }
 
 #[test]
@@ -333,6 +363,7 @@ fn test_bug_2600() {
     Abandoned 2 commits:
       vruxwmqv cedee197 b | b
       royxmykx 183dbbca a | a
+    Moved bookmarks to parents of abandoned commits: a, b
     Rebased 1 descendant commits onto parents of abandoned commits
     Working copy  (@) now at: znkkpsqq b350f44b c | c
     Parent commit (@-)      : zsuskuln 67c2f714 a b base | base
@@ -0,0 +1,362 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use indoc::indoc;
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_apply_to_working_copy() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\nb\nc\n");
+    work_dir.write_file("file2", "foo\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file1", "a\nB\nc\n");
+    work_dir.remove_file("file2");
+    work_dir.write_file("file3", "new\n");
+    let diff = work_dir.run_jj(["diff", "--git"]).success().stdout;
+    std::fs::write(test_env.env_root().join("patch.diff"), diff.raw()).unwrap();
+    work_dir.run_jj(["new", "@-"]).success();
+
+    let output = work_dir.run_jj(["apply", "../patch.diff"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: zsuskuln 952412f8 (no description set)
+    Parent commit (@-)      : qpvuntsm 7d437784 (no description set)
+    Added 1 files, modified 1 files, removed 1 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--git"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/file1 b/file1
+    index de980441c3..7be73ce3c1 100644
+    --- a/file1
+    +++ b/file1
+    @@ -1,3 +1,3 @@
+     a
+    -b
+    +B
+     c
+    diff --git a/file2 b/file2
+    deleted file mode 100644
+    index 257cc5642c..0000000000
+    --- a/file2
+    +++ /dev/null
+    @@ -1,1 +0,0 @@
+    -foo
+    diff --git a/file3 b/file3
+    new file mode 100644
+    index 0000000000..3e757656cf
+    --- /dev/null
+    +++ b/file3
+    @@ -0,0 +1,1 @@
+    +new
+    [EOF]
+    ");
+
+    // Applying the patch again fails because the hunks no longer match
+    let output = work_dir.run_jj(["apply", "../patch.diff"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to apply patch
+    Caused by: Hunk #1 does not apply to file1
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_apply_onto() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\nb\nc\n");
+    work_dir.run_jj(["commit", "-m", "base"]).success();
+
+    let patch = indoc! {"
+        From 0123456789abcdef0123456789abcdef01234567 Mon Sep 17 00:00:00 2001
+        From: Some One <some.one@example.com>
+        Date: Sat, 3 Feb 2001 04:05:06 +0700
+        Subject: [PATCH] Change b
+
+        Longer description.
+        ---
+         file | 2 +-
+         1 file changed, 1 insertion(+), 1 deletion(-)
+
+        diff --git a/file b/file
+        index 0123456..789abcd 100644
+        --- a/file
+        +++ b/file
+        @@ -1,3 +1,3 @@
+         a
+        -b
+        +B
+         c
+        --
+        2.40.0
+    "};
+    let output =
+        work_dir.run_jj_with(|cmd| cmd.args(["apply", "--onto", "@-", "-"]).write_stdin(patch));
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created new commit kkmpptxz 27dc95ed Change b
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "log",
+        "-r",
+        "children(@-) ~ @",
+        "-T",
+        r#"separate("\n", author, author.timestamp(), description)"#,
+        "--no-graph",
+        "--git",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    Some One <some.one@example.com>
+    2001-02-03 04:05:06.000 +07:00
+    Change b
+
+    Longer description.
+    diff --git a/file b/file
+    index de980441c3..7be73ce3c1 100644
+    --- a/file
+    +++ b/file
+    @@ -1,3 +1,3 @@
+     a
+    -b
+    +B
+     c
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_apply_errors() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj_with(|cmd| cmd.args(["apply", "-"]).write_stdin("not a patch\n"));
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No file changes found in the patch
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.args(["apply", "-"])
+            .write_stdin("--- a/file\n+++ b/file\n@@ -1 +1 @@\n-a\n+b\n")
+    });
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to apply patch
+    Caused by: File file does not exist
+    [EOF]
+    [exit status: 1]
+    ");
+    // Renames must not overwrite existing files
+    work_dir.write_file("file1", "a\n");
+    work_dir.write_file("file2", "b\n");
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.args(["apply", "-"]).write_stdin(indoc! {"
+            diff --git a/file1 b/file2
+            similarity index 100%
+            rename from file1
+            rename to file2
+        "})
+    });
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to apply patch
+    Caused by: File file2 already exists
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_apply_same_file_twice() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\nb\nc\n");
+    work_dir.run_jj(["new"]).success();
+
+    // The second file patch applies on top of the first one
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.args(["apply", "-"]).write_stdin(indoc! {"
+            --- a/file
+            +++ b/file
+            @@ -1 +1 @@
+            -a
+            +A
+            --- a/file
+            +++ b/file
+            @@ -3 +3 @@
+            -c
+            +C
+        "})
+    });
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: rlvkpnrz 390153cc (no description set)
+    Parent commit (@-)      : qpvuntsm f029d753 (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "file"]);
+    insta::assert_snapshot!(output, @r"
+    A
+    b
+    C
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_apply_exported_series() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["commit", "-m", "base"]).success();
+    work_dir.write_file("file", "b\n");
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    work_dir.write_file("file", "c\n");
+    work_dir.write_file("new", "new\n");
+    work_dir
+        .run_jj(["commit", "-m", "second\n\nWith description."])
+        .success();
+    let output = work_dir
+        .run_jj(["export", "-r=@--|@-", "--stdout"])
+        .success();
+    std::fs::write(test_env.env_root().join("series.mbox"), output.stdout.raw()).unwrap();
+
+    // Each patch in the series becomes a commit
+    let output = work_dir.run_jj([
+        "apply",
+        "--onto=description(exact:'base\n')",
+        "../series.mbox",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created new commit royxmykx 91ca7e3f first
+    Created new commit nppvrztz 5c803d89 second
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-r=..", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    @  zsuskuln test.user@example.com 2001-02-03 08:05:10 3c740bf1
+    │  (empty) (no description set)
+    ○  kkmpptxz test.user@example.com 2001-02-03 08:05:10 88d0eecb
+    │  second
+    │  M file
+    │  A new
+    ○  rlvkpnrz test.user@example.com 2001-02-03 08:05:09 a1df795c
+    │  first
+    │  M file
+    │ ○  nppvrztz test.user@example.com 2001-02-03 08:05:12 5c803d89
+    │ │  second
+    │ │  M file
+    │ │  A new
+    │ ○  royxmykx test.user@example.com 2001-02-03 08:05:12 91ca7e3f
+    ├─╯  first
+    │    M file
+    ○  qpvuntsm test.user@example.com 2001-02-03 08:05:08 cb0f0306
+    │  base
+    ~  A file
+    [EOF]
+    ");
+
+    // Multiple patch files can be applied to the working copy
+    work_dir
+        .run_jj(["export", "-r=@--|@-", "-o=../patches"])
+        .success();
+    work_dir.run_jj(["new", "@---"]).success();
+    let output = work_dir.run_jj([
+        "apply",
+        "../patches/0001-first.patch",
+        "../patches/0002-second.patch",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: yostqsxw 96ed4a11 (no description set)
+    Parent commit (@-)      : qpvuntsm cb0f0306 base
+    Added 1 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    M file
+    A new
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_apply_exported_round_trip() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // Body lines starting with "From " and non-ASCII headers survive a round
+    // trip through export and apply
+    work_dir.write_file("file", "a\n");
+    work_dir
+        .run_jj([
+            "commit",
+            "--author=Jöhn Dœ <john@example.com>",
+            "-m",
+            "Sübject\n\nFrom now on, this is fixed.\nSigned-off-by: Jöhn Dœ <john@example.com>",
+        ])
+        .success();
+    let output = work_dir.run_jj(["export", "-r=@-", "--stdout"]).success();
+    insta::assert_snapshot!(output.stdout.raw().lines().take(4).collect::<Vec<_>>().join("\n"), @r"
+    From b9536db4543799d4d68289135b4b7ce9449a0d5c Mon Sep 17 00:00:00 2001
+    From: =?UTF-8?q?J=C3=B6hn_D=C5=93?= <john@example.com>
+    Date: Sat, 3 Feb 2001 04:05:08 +0700
+    Subject: [PATCH] =?UTF-8?q?S=C3=BCbject?=
+    ");
+    std::fs::write(test_env.env_root().join("patch.mbox"), output.stdout.raw()).unwrap();
+
+    let output = work_dir.run_jj(["apply", "--onto=root()", "../patch.mbox"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created new commit zsuskuln f72e8c30 Sübject
+    [EOF]
+    ");
+    let template = r#"separate(" ", change_id.short(), author) ++ "\n" ++ description"#;
+    let output = work_dir.run_jj(["log", "-r=root()+", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    zsuskulnrvyr Jöhn Dœ <john@example.com>
+    Sübject
+
+    From now on, this is fixed.
+    Signed-off-by: Jöhn Dœ <john@example.com>
+    qpvuntsmwlqt Jöhn Dœ <john@example.com>
+    Sübject
+
+    From now on, this is fixed.
+    Signed-off-by: Jöhn Dœ <john@example.com>
+    [EOF]
+    ");
+}
@@ -0,0 +1,207 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Read as _;
+use std::path::Path;
+
+use itertools::Itertools as _;
+
+use crate::common::TestEnvironment;
+
+fn list_tar_entries(data: impl std::io::Read) -> String {
+    let mut archive = tar::Archive::new(data);
+    archive
+        .entries()
+        .unwrap()
+        .map(|entry| {
+            let mut entry = entry.unwrap();
+            let header = entry.header();
+            let path = entry.path().unwrap().to_string_lossy().into_owned();
+            let mode = header.mode().unwrap();
+            let description = match header.entry_type() {
+                tar::EntryType::Symlink => {
+                    let target = entry.link_name().unwrap().unwrap();
+                    format!("-> {}", target.display())
+                }
+                _ => {
+                    let mut content = String::new();
+                    entry.read_to_string(&mut content).unwrap();
+                    format!("{content:?}")
+                }
+            };
+            format!("{mode:o} {path} {description}\n")
+        })
+        .join("")
+}
+
+fn list_zip_entries(path: &Path) -> String {
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+    (0..archive.len())
+        .map(|i| {
+            let mut file = archive.by_index(i).unwrap();
+            let mode = file.unix_mode().unwrap();
+            let name = file.name().to_owned();
+            let mut content = String::new();
+            file.read_to_string(&mut content).unwrap();
+            format!("{mode:o} {name} {content:?}\n")
+        })
+        .join("")
+}
+
+#[test]
+fn test_archive() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\n");
+    work_dir.create_dir("dir");
+    work_dir.write_file("dir/file2", "b\n");
+    work_dir
+        .run_jj(["file", "chmod", "x", "dir/file2"])
+        .success();
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    work_dir.write_file("file1", "changed\n");
+
+    // Doesn't include changes in the working copy
+    let output = work_dir.run_jj(["archive", "-r=@-", "-o=../out.tar"]);
+    insta::assert_snapshot!(output, @"");
+    let data = std::fs::read(test_env.env_root().join("out.tar")).unwrap();
+    insta::assert_snapshot!(list_tar_entries(data.as_slice()), @r#"
+    755 dir/file2 "b\n"
+    644 file1 "a\n"
+    "#);
+
+    // Can add a prefix and select paths
+    work_dir
+        .run_jj(["archive", "-r=@-", "-o=../out.tgz", "--prefix=proj/", "dir"])
+        .success();
+    let data = std::fs::read(test_env.env_root().join("out.tgz")).unwrap();
+    let decoder = flate2::read::GzDecoder::new(data.as_slice());
+    insta::assert_snapshot!(list_tar_entries(decoder), @r#"755 proj/dir/file2 "b\n""#);
+
+    // Zip archive
+    work_dir
+        .run_jj(["archive", "-o=../out.zip", "--prefix=proj/"])
+        .success();
+    insta::assert_snapshot!(list_zip_entries(&test_env.env_root().join("out.zip")), @r#"
+    100755 proj/dir/file2 "b\n"
+    100644 proj/file1 "changed\n"
+    "#);
+
+    // Format can be specified explicitly, and the archive can be written to stdout
+    let output = work_dir.run_jj(["archive", "-r=@-", "-o=-", "--format=tar", "file1"]);
+    insta::assert_snapshot!(
+        list_tar_entries(output.stdout.raw().as_bytes()),
+        @r#"644 file1 "a\n""#);
+
+    // Warning if path doesn't exist
+    let output = work_dir.run_jj(["archive", "-o=../out.tar", "file9"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No matching entries for paths: file9
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_archive_large_file() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"snapshot.max-new-file-size = "10MiB""#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // Larger than the size of files buffered in memory
+    let content = "0123456789abcdef".repeat(100_000);
+    work_dir.write_file("large", &content);
+    work_dir.write_file("small", "a\n");
+
+    work_dir.run_jj(["archive", "-o=../out.tar"]).success();
+    let data = std::fs::read(test_env.env_root().join("out.tar")).unwrap();
+    let mut archive = tar::Archive::new(data.as_slice());
+    let entries = archive
+        .entries()
+        .unwrap()
+        .map(|entry| {
+            let mut entry = entry.unwrap();
+            let path = entry.path().unwrap().to_string_lossy().into_owned();
+            let mut data = String::new();
+            entry.read_to_string(&mut data).unwrap();
+            (path, entry.header().size().unwrap(), data == content)
+        })
+        .collect_vec();
+    assert_eq!(
+        entries,
+        [
+            ("large".to_owned(), content.len() as u64, true),
+            ("small".to_owned(), 2, false),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_archive_write_error() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a\n");
+    work_dir.create_dir("out");
+
+    // The archive can't replace a directory, and no temporary file is left
+    let output = work_dir.run_jj(["archive", "-o=out"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to create $TEST_ENV/repo/out
+    Caused by: Is a directory (os error 21)
+    [EOF]
+    [exit status: 1]
+    ");
+    assert!(work_dir.root().join("out").is_dir());
+    let dir_entries = std::fs::read_dir(work_dir.root())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .sorted()
+        .collect_vec();
+    assert_eq!(dir_entries, [".jj", "file", "out"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_archive_symlink_and_conflict() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\n");
+    std::os::unix::fs::symlink("file", work_dir.root().join("link")).unwrap();
+    work_dir.run_jj(["new", "-m=b"]).success();
+    work_dir.write_file("file", "b\n");
+    work_dir.run_jj(["new", "@-", "-m=c"]).success();
+    work_dir.write_file("file", "c\n");
+    work_dir
+        .run_jj([
+            "new",
+            "description(substring:b)",
+            "description(substring:c)",
+        ])
+        .success();
+
+    work_dir.run_jj(["archive", "-o=../out.tar"]).success();
+    let data = std::fs::read(test_env.env_root().join("out.tar")).unwrap();
+    insta::assert_snapshot!(list_tar_entries(data.as_slice()), @r#"
+    644 file "<<<<<<< conflict 1 of 1\n%%%%%%% diff from: qpvuntsm 058c349f\n\\\\\\\\\\\\\\        to: rlvkpnrz c6e4e65d \"b\"\n-a\n+b\n+++++++ kkmpptxz b81d1e7f \"c\"\nc\n>>>>>>> conflict 1 of 1 ends\n"
+    777 link -> file
+    "#);
+}
@@ -518,21 +518,21 @@ fn test_default_command_is_resolved(shell: Shell) {
         Shell::Bash => {
             insta::assert_snapshot!(output, @r"
             --revisions
-            --limit
+            --follow
             [EOF]
             ");
         }
         Shell::Zsh => {
             insta::assert_snapshot!(output, @r"
             --revisions:Which revisions to show
-            --limit:Limit number of revisions to show
+            --follow:Follow the given file paths across renames and copies
             [EOF]
             ");
         }
         Shell::Fish => {
             insta::assert_snapshot!(output, @r"
             --revisions	Which revisions to show
-            --limit	Limit number of revisions to show
+            --follow	Follow the given file paths across renames and copies
             [EOF]
             ");
         }
@@ -545,23 +545,23 @@ fn test_default_command_is_resolved(shell: Shell) {
         .take_stdout_n_lines(2);
     match shell {
         Shell::Bash => {
-            insta::assert_snapshot!(output, @r"
+            insta::assert_snapshot!(output, @"
             --retain-bookmarks
-            --restore-descendants
+            --delete-bookmarks
             [EOF]
             ");
         }
         Shell::Zsh => {
-            insta::assert_snapshot!(output, @r"
+            insta::assert_snapshot!(output, @"
             --retain-bookmarks:Do not delete bookmarks pointing to the revisions to abandon
-            --restore-descendants:Do not modify the content of the children of the abandoned commits
+            --delete-bookmarks:Delete bookmarks pointing to the revisions to abandon
             [EOF]
             ");
         }
         Shell::Fish => {
-            insta::assert_snapshot!(output, @r"
+            insta::assert_snapshot!(output, @"
             --retain-bookmarks	Do not delete bookmarks pointing to the revisions to abandon
-            --restore-descendants	Do not modify the content of the children of the abandoned commits
+            --delete-bookmarks	Delete bookmarks pointing to the revisions to abandon
             [EOF]
             ");
         }
@@ -1253,6 +1253,7 @@ fn test_merge_tools() {
     insta::assert_snapshot!(output, @r"
     :summary
     :stat
+    :numstat
     :types
     :name-only
     :git
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::path::Path;
 use std::path::PathBuf;
 
 use indoc::indoc;
@@ -19,6 +20,7 @@ use indoc::indoc;
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 use crate::common::TestWorkDir;
+use crate::common::fake_editor_path;
 use crate::common::force_interactive;
 
 #[test]
@@ -266,6 +268,32 @@ fn test_describe_editor_env() {
     ");
 }
 
+#[test]
+fn test_describe_editor_in_home_dir() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let editor_name = Path::new(&fake_editor_path())
+        .file_name()
+        .unwrap()
+        .to_owned();
+    std::fs::copy(fake_editor_path(), test_env.home_dir().join(&editor_name)).unwrap();
+    let edit_script = test_env.env_root().join("edit_script");
+    std::fs::write(&edit_script, "write\ndescription from editor in home").unwrap();
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.arg("describe")
+            .env("JJ_EDITOR", format!("~/{}", editor_name.to_str().unwrap()))
+            .env("EDIT_SCRIPT", &edit_script)
+    });
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: qpvuntsm b541116b (empty) description from editor in home
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_describe_no_matching_revisions() {
     let test_env = TestEnvironment::default();
@@ -332,6 +332,7 @@ fn test_diff_basic() {
     error: the argument '--template <TEMPLATE>' cannot be used with:
       --summary
       --stat
+      --numstat
       --types
       --name-only
 
@@ -669,6 +670,30 @@ fn test_diff_name_only() {
     ");
 }
 
+#[test]
+fn test_diff_numstat() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("deleted", "a\nb\n");
+    work_dir.write_file("modified", "a\nb\n");
+    work_dir.write_file("renamed", "a\nb\nc\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.remove_file("deleted");
+    work_dir.write_file("modified", "a\nc\nd\n");
+    work_dir.remove_file("renamed");
+    work_dir.write_file("renamed2", "a\nb\nc\n");
+    work_dir.write_file("binary", b"\0binary");
+    insta::assert_snapshot!(work_dir.run_jj(["diff", "--numstat"]), @"
+    -	-	binary
+    0	2	deleted
+    2	1	modified
+    0	0	{renamed => renamed2}
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diff_renamed_file_and_dir() {
     let test_env = TestEnvironment::default();
@@ -705,6 +730,54 @@ fn test_diff_renamed_file_and_dir() {
     ");
 }
 
+#[test]
+fn test_diff_follow() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("old", "a\nb\nc\n");
+    work_dir.write_file("other", "foo\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.remove_file("old");
+    work_dir.write_file("new", "a\nb\nc\nd\n");
+    work_dir.write_file("other", "bar\n");
+
+    let output = work_dir.run_jj(["diff", "--summary", "old"]);
+    insta::assert_snapshot!(output, @r"
+    D old
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--summary", "new"]);
+    insta::assert_snapshot!(output, @r"
+    R {old => new}
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--git", "--follow", "old"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/old b/new
+    rename from old
+    rename to new
+    index de980441c3..d68dd4031d 100644
+    --- a/old
+    +++ b/new
+    @@ -1,3 +1,4 @@
+     a
+     b
+     c
+    +d
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--summary", "--follow", "--from=@-", "old"]);
+    insta::assert_snapshot!(output, @r"
+    R {old => new}
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diff_bad_args() {
     let test_env = TestEnvironment::default();
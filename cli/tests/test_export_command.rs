@@ -0,0 +1,138 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_export_git_patch() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\nb\n");
+    work_dir
+        .run_jj(["commit", "-m", "Add file\n\nWith a longer description."])
+        .success();
+    work_dir.write_file("file", "a\nB\n");
+    work_dir.write_file("other", "new\n");
+    work_dir
+        .run_jj(["commit", "-m", "Change b: uppercase"])
+        .success();
+
+    let output = work_dir.run_jj(["export", "-r=::@- ~ root()", "-o=../patches"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    ../patches/0001-Add-file.patch
+    ../patches/0002-Change-b-uppercase.patch
+    [EOF]
+    ");
+    let patch = std::fs::read_to_string(
+        test_env
+            .env_root()
+            .join("patches/0002-Change-b-uppercase.patch"),
+    )
+    .unwrap();
+    insta::assert_snapshot!(patch, @r"
+    From e76d1b8acbb4dd56076ca8c3fd535f4f6c8164bd Mon Sep 17 00:00:00 2001
+    From: Test User <test.user@example.com>
+    Date: Sat, 3 Feb 2001 04:05:09 +0700
+    Subject: [PATCH 2/2] Change b: uppercase
+
+    ---
+    file  | 2 +-
+    other | 1 +
+    2 files changed, 2 insertions(+), 1 deletion(-)
+
+    diff --git a/file b/file
+    index 422c2b7ab3..55dce135f5 100644
+    --- a/file
+    +++ b/file
+    @@ -1,2 +1,2 @@
+     a
+    -b
+    +B
+    diff --git a/other b/other
+    new file mode 100644
+    index 0000000000..3e757656cf
+    --- /dev/null
+    +++ b/other
+    @@ -0,0 +1,1 @@
+    +new
+    -- 
+    jj 0.36.0
+    ");
+
+    // Single patch to stdout
+    let output = work_dir.run_jj(["export", "-r=@--", "--stdout"]);
+    insta::assert_snapshot!(output, @r"
+    From 761d9f8232ef39877e98dd308a01e7c14b1af5bd Mon Sep 17 00:00:00 2001
+    From: Test User <test.user@example.com>
+    Date: Sat, 3 Feb 2001 04:05:08 +0700
+    Subject: [PATCH] Add file
+
+    With a longer description.
+    ---
+    file | 2 ++
+    1 file changed, 2 insertions(+), 0 deletions(-)
+
+    diff --git a/file b/file
+    new file mode 100644
+    index 0000000000..422c2b7ab3
+    --- /dev/null
+    +++ b/file
+    @@ -0,0 +1,2 @@
+    +a
+    +b
+    -- 
+    jj 0.36.0
+
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_export_errors() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["new", "root()", "-m=a"]).success();
+    work_dir.run_jj(["new", "root()", "-m=b"]).success();
+    work_dir
+        .run_jj([
+            "new",
+            "description(substring:a)",
+            "description(substring:b)",
+        ])
+        .success();
+
+    let output = work_dir.run_jj(["export", "-r=@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot export merge commit 5b7988a909b4
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["export", "-r=root()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot export the root commit
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["export", "-r=none()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No revisions to export
+    [EOF]
+    ");
+}
@@ -93,3 +93,40 @@ fn test_file_list() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_file_list_no_recurse() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.create_dir_all("dir/sub");
+    work_dir.write_file("dir/file", "content");
+    work_dir.write_file("dir/sub/file1", "content1");
+    work_dir.write_file("dir/sub/file2", "content2");
+    work_dir.write_file("file", "longer content\n");
+
+    let template = r#"separate(" ", path, "[" ++ file_type ++ "]", size) ++ "\n""#;
+    let output = work_dir.run_jj(["file", "list", "--no-recurse", "-T", template]);
+    insta::assert_snapshot!(output.normalize_backslash(), @"
+    dir [tree] 0
+    file [file] 15
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["file", "list", "--no-recurse", "-T", template, "dir"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @"
+    dir/file [file] 7
+    dir/sub [tree] 0
+    [EOF]
+    ");
+
+    // Files and nested directories can be listed together
+    let output = work_dir.run_jj(["file", "list", "--no-recurse", "dir/sub", "file"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @"
+    dir/sub/file1
+    dir/sub/file2
+    file
+    [EOF]
+    ");
+}
@@ -61,6 +61,124 @@ fn test_file_search() {
     ");
 }
 
+#[test]
+fn test_file_search_pattern_kinds() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n-foo-\n");
+    work_dir.write_file("file2", "-bar-\nBAR\n");
+    work_dir.write_file("file3", "a:b\n");
+
+    // Can select the pattern kind
+    let output = work_dir.run_jj(["file", "search", "--pattern=substring:foo"]);
+    insta::assert_snapshot!(output, @r"
+    file1
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "search", "--pattern=regex:^-(foo|bar)-$"]);
+    insta::assert_snapshot!(output, @r"
+    file1
+    file2
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "search", "--pattern=exact-i:bar"]);
+    insta::assert_snapshot!(output, @r"
+    file2
+    [EOF]
+    ");
+
+    // Patterns without a known kind are globs
+    let output = work_dir.run_jj(["file", "search", "--pattern=a:b"]);
+    insta::assert_snapshot!(output, @r"
+    file3
+    [EOF]
+    ");
+
+    // Invalid pattern
+    let output = work_dir.run_jj(["file", "search", "--pattern=regex:("]);
+    insta::assert_snapshot!(output.strip_stderr_last_line(), @r"
+    ------- stderr -------
+    Error: regex parse error:
+        (
+        ^
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_file_search_lines() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\nbar\nfoobar\n");
+    work_dir.write_file("file2", "bar\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file2", "bar\nfoo");
+
+    let output = work_dir.run_jj(["file", "search", "--pattern=*foo*", "--lines"]);
+    insta::assert_snapshot!(output, @r"
+    file1:1:foo
+    file1:3:foobar
+    file2:2:foo
+    [EOF]
+    ");
+
+    // Results are attributed to commits when searching multiple revisions
+    let output = work_dir.run_jj(["file", "search", "--pattern=*foo*", "-r=@|@-"]);
+    insta::assert_snapshot!(output, @r"
+    3714996eaccc:file1
+    3714996eaccc:file2
+    9abe7d242709:file1
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "file",
+        "search",
+        "--pattern=*foo*",
+        "-r=@-",
+        "-r=@",
+        "--lines",
+        "file2",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    3714996eaccc:file2:2:foo
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["file", "search", "--pattern=*foo*", "-r=none()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Empty revision set
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_file_search_sparse() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n");
+    work_dir.create_dir("dir");
+    work_dir.write_file("dir/file2", "foo\n");
+    work_dir
+        .run_jj(["sparse", "set", "--clear", "--add=dir"])
+        .success();
+
+    // Files outside the sparse patterns aren't searched
+    let output = work_dir.run_jj(["file", "search", "--pattern=foo"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    dir/file2
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_file_search_conflicts() {
     let test_env = TestEnvironment::default();
@@ -107,4 +225,12 @@ fn test_file_search_conflicts() {
     // Doesn't list file if the pattern doesn't match
     let output = work_dir.run_jj(["file", "search", "--pattern=*qux*"]);
     insta::assert_snapshot!(output.normalize_backslash(), @"");
+
+    // Prints matching lines of each side
+    let output = work_dir.run_jj(["file", "search", "--pattern=-*-", "--lines"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    file1:1:-foo-
+    file1:1:-baz-
+    [EOF]
+    ");
 }
@@ -157,3 +157,56 @@ fn test_show_symlink() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_show_output_dir() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\n");
+    work_dir.create_dir("dir");
+    work_dir.write_file("dir/file2", "b\n");
+    work_dir.write_file("dir/file3.txt", "c\n");
+    work_dir
+        .run_jj(["file", "chmod", "x", "dir/file2"])
+        .success();
+    work_dir.run_jj(["new"]).success();
+    work_dir.remove_file("file1");
+
+    // Files are written preserving their paths
+    work_dir
+        .run_jj([
+            "file",
+            "show",
+            "-r@-",
+            "--output-dir=../out",
+            "file1",
+            "glob:dir/*.txt",
+        ])
+        .success();
+    let out_dir = test_env.env_root().join("out");
+    assert_eq!(
+        std::fs::read_to_string(out_dir.join("file1")).unwrap(),
+        "a\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(out_dir.join("dir").join("file3.txt")).unwrap(),
+        "c\n"
+    );
+    assert!(!out_dir.join("dir").join("file2").exists());
+
+    // Executable bit is preserved
+    work_dir
+        .run_jj(["file", "show", "--output-dir=../out", "dir"])
+        .success();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt as _;
+        let mode = std::fs::metadata(out_dir.join("dir").join("file2"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o111, 0o111);
+    }
+}
@@ -1193,6 +1193,26 @@ fn test_git_clone_no_git_executable_with_path() {
     "#);
 }
 
+#[test]
+fn test_git_clone_git_executable_in_home_dir() {
+    let test_env = TestEnvironment::default();
+    let root_dir = test_env.work_dir("");
+    test_env.add_config("git.executable-path = '~/invalid/git'");
+    let git_repo_path = test_env.env_root().join("source");
+    let git_repo = git::init(git_repo_path);
+    set_up_non_empty_git_repo(&git_repo);
+
+    // The leading `~/` is expanded to the home directory
+    let output = root_dir.run_jj(["git", "clone", "source", "clone"]);
+    insta::assert_snapshot!(output.strip_stderr_last_line(), @r#"
+    ------- stderr -------
+    Fetching into new repo in "$TEST_ENV/clone"
+    Error: Could not execute git process at specified path '$TEST_ENV/home/invalid/git'
+    [EOF]
+    [exit status: 1]
+    "#);
+}
+
 #[test]
 fn test_git_clone_branch() {
     let test_env = TestEnvironment::default();
@@ -216,6 +216,58 @@ fn test_git_fetch_single_remote_from_config() {
     ");
 }
 
+#[test]
+fn test_git_fetch_bookmarks_from_config() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("remotes.rem1.auto-track-bookmarks = '*'");
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = add_git_remote(&test_env, &work_dir, "rem1");
+    add_commit_to_branch(&git_repo, "foo", "message foo");
+    add_commit_to_branch(&git_repo, "bar", "message bar");
+    test_env.add_config("remotes.rem1.fetch-bookmarks = 'rem1 | f*'");
+
+    work_dir.run_jj(["git", "fetch"]).success();
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    foo: npoyxmom 2f1554a5 message foo
+      @rem1: npoyxmom 2f1554a5 message foo
+    rem1: ppspxspk 4acd0343 message
+      @rem1: ppspxspk 4acd0343 message
+    [EOF]
+    ");
+
+    // Bookmarks specified on the command line take precedence
+    work_dir.run_jj(["git", "fetch", "-b", "bar"]).success();
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    bar: tuqsmquo 7fbde5b6 message bar
+      @rem1: tuqsmquo 7fbde5b6 message bar
+    foo: npoyxmom 2f1554a5 message foo
+      @rem1: npoyxmom 2f1554a5 message foo
+    rem1: ppspxspk 4acd0343 message
+      @rem1: ppspxspk 4acd0343 message
+    [EOF]
+    ");
+
+    // Invalid pattern
+    test_env.add_config("remotes.rem1.fetch-bookmarks = '('");
+    let output = work_dir.run_jj(["git", "fetch"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Hint: Fetching from the only existing remote: rem1
+    Config error: Invalid `remotes.rem1.fetch-bookmarks`: Syntax error
+    Caused by:  --> 1:2
+      |
+    1 | (
+      |  ^---
+      |
+      = expected <expression>
+    Hint: See https://docs.jj-vcs.dev/latest/revsets/ or use `jj help -k revsets` for revsets syntax and how to quote symbols.
+    For help, see https://docs.jj-vcs.dev/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_git_fetch_multiple_remotes() {
     let test_env = TestEnvironment::default();
@@ -170,6 +170,65 @@ fn test_git_push_current_bookmark() {
     ");
 }
 
+#[test]
+fn test_git_push_default_bookmarks_config() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    test_env.add_config("remotes.origin.auto-track-bookmarks = '*'");
+    test_env.add_config("remotes.origin.push-bookmarks = '~my-*'");
+    let work_dir = test_env.work_dir("local");
+    work_dir.run_jj(["new", "bookmark2", "-m", "foo"]).success();
+    work_dir
+        .run_jj(["bookmark", "set", "bookmark2", "-r@"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "my-bookmark"])
+        .success();
+
+    // `my-bookmark` is excluded from the default push revset
+    let output = work_dir.run_jj(["git", "push", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move forward bookmark bookmark2 from 38a204733702 to 86ea093ebb2e
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+
+    // Explicitly selected revisions aren't filtered
+    let output = work_dir.run_jj(["git", "push", "--dry-run", "-r@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move forward bookmark bookmark2 from 38a204733702 to 86ea093ebb2e
+      Add bookmark my-bookmark to 86ea093ebb2e
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+
+    // Invalid expression
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        "--dry-run",
+        "--config=remotes.origin.push-bookmarks='('",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: Invalid `remotes.origin.push-bookmarks`: Syntax error
+    Caused by:  --> 1:2
+      |
+    1 | (
+      |  ^---
+      |
+      = expected <expression>
+    Hint: See https://docs.jj-vcs.dev/latest/revsets/ or use `jj help -k revsets` for revsets syntax and how to quote symbols.
+    For help, see https://docs.jj-vcs.dev/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_git_push_parent_bookmark() {
     let test_env = TestEnvironment::default();
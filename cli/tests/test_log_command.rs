@@ -132,6 +132,19 @@ fn test_log_with_diff_stats() {
     ◆
     [EOF]
     ");
+
+    // Stats queried more than once per commit, with and without fileset
+    let template = r#"
+    self.diff().stat().total_added() ++ " " ++ self.diff().stat().total_removed() ++ " "
+    ++ self.diff("file1").stat().total_added() ++ "\n"
+    "#;
+    let output = work_dir.run_jj(["log", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    @  2 3 1
+    ○  7 0 1
+    ◆  0 0 0
+    [EOF]
+    ");
 }
 
 #[test]
@@ -268,6 +281,17 @@ fn test_log_with_or_without_diff() {
     [EOF]
     ");
 
+    // `--numstat` prints line counts per file
+    let output = work_dir.run_jj(["log", "-T", "description", "--numstat"]);
+    insta::assert_snapshot!(output, @"
+    @  a new commit
+    │  1	0	file1
+    ○  add a file
+    │  1	0	file1
+    ◆
+    [EOF]
+    ");
+
     // `-p` enables default "summary" output, so `-s` is noop
     let output = work_dir.run_jj([
         "log",
@@ -1009,6 +1033,94 @@ fn test_log_filtered_by_path() {
     ");
 }
 
+#[test]
+fn test_log_follow_renames() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("old", "a\nb\nc\n");
+    work_dir.run_jj(["commit", "-m", "add old"]).success();
+    work_dir.write_file("old", "a\nb\nc\nd\n");
+    work_dir.run_jj(["commit", "-m", "modify old"]).success();
+    work_dir.remove_file("old");
+    work_dir.write_file("new", "a\nb\nc\nd\n");
+    work_dir
+        .run_jj(["commit", "-m", "rename old to new"])
+        .success();
+    work_dir.write_file("new", "a\nb\nc\nd\ne\n");
+    work_dir.run_jj(["commit", "-m", "modify new"]).success();
+    // Unrelated file reusing the old path isn't followed
+    work_dir.write_file("old", "unrelated\n");
+    work_dir
+        .run_jj(["describe", "-m", "add another old"])
+        .success();
+
+    let output = work_dir.run_jj(["log", "-T", "description", "-s", "new"]);
+    insta::assert_snapshot!(output, @r"
+    ○  modify new
+    │  M new
+    ○  rename old to new
+    │  R {old => new}
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "-T", "description", "-s", "--follow", "new"]);
+    insta::assert_snapshot!(output, @r"
+    ○  modify new
+    │  M new
+    ○  rename old to new
+    │  R {old => new}
+    ○  modify old
+    │  M old
+    ○  add old
+    │  A old
+    ~
+    [EOF]
+    ");
+
+    // Other patterns are matched without following renames
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "description",
+        "-s",
+        "--follow",
+        "new",
+        "glob:ol*",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  add another old
+    │  A old
+    ○  modify new
+    │  M new
+    ○  rename old to new
+    │  R {old => new}
+    ○  modify old
+    │  M old
+    ○  add old
+    │  A old
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "-T", "description", "--follow", "-n2", "new"]);
+    insta::assert_snapshot!(output, @r"
+    ○  modify new
+    ○  rename old to new
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "-T", "description", "--follow", "glob:n*"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: --follow requires file paths to be specified
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_log_limit() {
     let test_env = TestEnvironment::default();
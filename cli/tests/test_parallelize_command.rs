@@ -15,6 +15,7 @@
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 use crate::common::TestWorkDir;
+use crate::common::create_commit_with_files;
 
 #[test]
 fn test_parallelize_no_descendants() {
@@ -753,6 +754,45 @@ fn test_parallelize_no_immutable_non_base_commits() {
     "#);
 }
 
+#[test]
+fn test_parallelize_overlapping_paths() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "a", &[], &[("file1", "a\n"), ("file2", "a\n")]);
+    create_commit_with_files(&work_dir, "b", &["a"], &[("file1", "b\n")]);
+    create_commit_with_files(&work_dir, "c", &["b"], &[("file3", "c\n")]);
+
+    let output = work_dir.run_jj(["parallelize", "a::c"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: The following paths are modified by more than one of the parallelized revisions:
+      file1
+    Working copy  (@) now at: royxmykx 9bbe870d c | c
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 0 files, modified 0 files, removed 2 files
+    New conflicts appeared in 1 commits:
+      zsuskuln 414ae8c5 b | (conflict) b
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new zsuskuln
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  9bbe870d2c56 c parents:
+    │ ×  414ae8c50eca b parents:
+    ├─╯
+    │ ○  16651d70099a a parents:
+    ├─╯
+    ◆  000000000000 parents:
+    [EOF]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = r#"
@@ -2791,6 +2791,58 @@ fn test_rebase_skip_emptied() {
     ");
 }
 
+#[test]
+fn test_rebase_dry_run() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    work_dir
+        .run_jj(["new", "a", "-m", "will become empty"])
+        .success();
+    work_dir.run_jj(["restore", "--from=b"]).success();
+    work_dir.run_jj(["new", "-m", "already empty"]).success();
+    let setup_opid = work_dir.current_operation_id();
+
+    let output = work_dir.run_jj(["rebase", "-d=b", "--skip-emptied", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    Rebase plan:
+      vruxwmqv 57445ae7 (empty) already empty
+        onto zsuskuln 123b4d91 b | b
+    Commits to abandon:
+      royxmykx dc725230 will become empty
+    [EOF]
+    ------- stderr -------
+    Dry-run requested, not rebasing.
+    [EOF]
+    ");
+
+    // The plan is printed even with --quiet
+    let output = work_dir.run_jj(["rebase", "-d=b", "--skip-emptied", "--dry-run", "--quiet"]);
+    insta::assert_snapshot!(output, @r"
+    Rebase plan:
+      vruxwmqv c6ca49b9 (empty) already empty
+        onto zsuskuln 123b4d91 b | b
+    Commits to abandon:
+      royxmykx dc725230 will become empty
+    [EOF]
+    ");
+
+    // Nothing was rebased
+    assert_eq!(work_dir.current_operation_id(), setup_opid);
+    insta::assert_snapshot!(work_dir.run_jj(["log", "-T", "description"]), @r"
+    @  already empty
+    ○  will become empty
+    │ ○  b
+    ├─╯
+    ○  a
+    ◆
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_rebase_skip_emptied_descendants() {
     let test_env = TestEnvironment::default();
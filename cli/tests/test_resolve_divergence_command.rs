@@ -0,0 +1,356 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::CommandOutput;
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+use crate::common::create_commit;
+
+/// Makes commit `name` divergent by describing it concurrently.
+fn make_divergent(work_dir: &TestWorkDir, name: &str) {
+    work_dir
+        .run_jj(["describe", "-r", name, "-m", &format!("{name} one")])
+        .success();
+    work_dir
+        .run_jj([
+            "describe",
+            "--at-op=@-",
+            "-r",
+            name,
+            "-m",
+            &format!("{name} two"),
+        ])
+        .success();
+}
+
+#[must_use]
+fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
+    let template = r#"separate(" ", change_id.short(), description.first_line(), bookmarks)"#;
+    work_dir.run_jj(["log", "-r", "all()", "-T", template])
+}
+
+#[test]
+fn test_resolve_divergence_list() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    let output = work_dir.run_jj(["resolve-divergence"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No divergent changes found.
+    [EOF]
+    ");
+
+    make_divergent(&work_dir, "a");
+    let output = work_dir.run_jj(["resolve-divergence"]);
+    insta::assert_snapshot!(output, @r"
+    Change zsuskulnrvyr has 2 visible commits:
+      zsuskuln/0 b7ae5ac9 b?? | (divergent) b
+      zsuskuln/1 37b1a7b2 b?? | (divergent) b
+    Change rlvkpnrzqnoo has 2 visible commits:
+      rlvkpnrz/0 872a5bc7 a?? | (divergent) a two
+      rlvkpnrz/1 41d8851d a?? | (divergent) a one
+    [EOF]
+    ------- stderr -------
+    Concurrent modification detected, resolving automatically.
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_resolve_divergence_keep() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    make_divergent(&work_dir, "a");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  zsuskulnrvyr b b??
+    ○  rlvkpnrzqnoo a one a??
+    │ ○  zsuskulnrvyr b b??
+    │ ○  rlvkpnrzqnoo a two a??
+    ├─╯
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ------- stderr -------
+    Concurrent modification detected, resolving automatically.
+    [EOF]
+    ");
+
+    // Only one commit of each change can be kept
+    let output = work_dir.run_jj(["resolve-divergence", "--keep", "subject('a *')"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: More than one commit of divergent change rlvkpnrzqnoo was specified with --keep
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The descendants of the abandoned commit are rebased onto the kept commit
+    let output = work_dir.run_jj(["resolve-divergence", "--keep", "subject('a two')"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Resolved 1 divergent changes by keeping:
+      rlvkpnrz 7ee4a759 a | a two
+    Abandoned 1 commits:
+      rlvkpnrz/1 2df3ca61 a?? | (divergent) a one
+    Rebased 1 descendant commits
+    Working copy  (@) now at: zsuskuln/0 bcd543e9 b?? | (divergent) b
+    Parent commit (@-)      : rlvkpnrz 7ee4a759 a | a two
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  zsuskulnrvyr b b??
+    │ ○  zsuskulnrvyr b b??
+    ├─╯
+    ○  rlvkpnrzqnoo a two a
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_resolve_divergence_keep_newest() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    make_divergent(&work_dir, "a");
+
+    let output = work_dir.run_jj(["resolve-divergence", "--keep-newest"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Concurrent modification detected, resolving automatically.
+    Resolved 2 divergent changes by keeping:
+      zsuskuln 1a171b29 b | b
+      rlvkpnrz 7ee4a759 a | a two
+    Abandoned 2 commits:
+      zsuskuln/1 d5666c60 b?? | (divergent) b
+      rlvkpnrz/1 2df3ca61 a?? | (divergent) a one
+    Working copy  (@) now at: zsuskuln 1a171b29 b | b
+    Parent commit (@-)      : rlvkpnrz 7ee4a759 a | a two
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  zsuskulnrvyr b b
+    ○  rlvkpnrzqnoo a two a
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_resolve_divergence_update_change_ids() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    make_divergent(&work_dir, "a");
+
+    let output = work_dir.run_jj([
+        "resolve-divergence",
+        "--keep=subject('a one')",
+        "--update-change-ids",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Concurrent modification detected, resolving automatically.
+    Resolved 1 divergent changes by keeping:
+      rlvkpnrz 2df3ca61 a?? | a one
+    Assigned new change IDs to 1 commits:
+      vruxwmqv a7f2afe1 a?? | a two
+    Rebased 1 descendant commits
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  zsuskulnrvyr b b??
+    ○  rlvkpnrzqnoo a one a??
+    │ ○  zsuskulnrvyr b b??
+    │ ○  vruxwmqvtpmx a two a??
+    ├─╯
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    // --update-change-ids requires choosing the commit to keep
+    let output = work_dir.run_jj(["resolve-divergence", "--update-change-ids"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      <--keep <REVSETS>|--keep-newest|--interactive>
+
+    Usage: jj resolve-divergence --update-change-ids <--keep <REVSETS>|--keep-newest|--interactive> [REVSETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_resolve_divergence_merge() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+    // Make the divergent commits differ in content as well as description
+    work_dir
+        .run_jj(["describe", "-r", "b", "-m", "b one"])
+        .success();
+    work_dir
+        .run_jj([
+            "squash",
+            "--at-op=@-",
+            "--from=c",
+            "--into=b",
+            "--use-destination-message",
+        ])
+        .success();
+
+    let output = work_dir.run_jj(["resolve-divergence", "--keep=subject('b one')", "--merge"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Concurrent modification detected, resolving automatically.
+    Resolved 1 divergent changes by keeping:
+      zsuskuln b92426ba b c?? | b one
+    Squashed 1 commits into the kept commits:
+      zsuskuln/0 eeb66892 b?? c?? | (divergent) b
+    Rebased 2 descendant commits
+    Working copy  (@) now at: royxmykx 11a53aae c?? | (empty) c
+    Parent commit (@-)      : zsuskuln b92426ba b c?? | b one
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  royxmykxtrkr c c??
+    │ ○  yostqsxwqrlt
+    ├─╯
+    ○  zsuskulnrvyr b one b c??
+    ○  rlvkpnrzqnoo a a
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "list", "-r=b"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    b
+    c
+    [EOF]
+    ");
+
+    // --merge and --update-change-ids are mutually exclusive
+    let output = work_dir.run_jj([
+        "resolve-divergence",
+        "--keep-newest",
+        "--merge",
+        "--update-change-ids",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--merge' cannot be used with '--update-change-ids'
+
+    Usage: jj resolve-divergence --merge <--keep <REVSETS>|--keep-newest|--interactive> [REVSETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_resolve_divergence_merge_without_descendants() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    work_dir.run_jj(["new", "root()"]).success();
+    make_divergent(&work_dir, "b");
+
+    let output = work_dir.run_jj(["resolve-divergence", "--keep-newest", "--merge"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Concurrent modification detected, resolving automatically.
+    Resolved 1 divergent changes by keeping:
+      zsuskuln 52f3d06c b | b two
+    Squashed 1 commits into the kept commits:
+      zsuskuln/1 9b9a387f b?? | (divergent) b one
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  royxmykxtrkr
+    │ ○  zsuskulnrvyr b two b
+    │ ○  rlvkpnrzqnoo a a
+    ├─╯
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_resolve_divergence_immutable() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    make_divergent(&work_dir, "a");
+    test_env.add_config("revset-aliases.'immutable_heads()' = 'subject(\"a one\")'");
+
+    let output = work_dir.run_jj(["resolve-divergence", "-r=all()", "--keep=subject('a two')"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Concurrent modification detected, resolving automatically.
+    Error: Commit de277eaeb26b is immutable
+    Hint: Could not modify commit: rlvkpnrz/1 de277eae a?? | (divergent) a one
+    Hint: Immutable commits are used to protect shared history.
+    Hint: For more information, see:
+          - https://docs.jj-vcs.dev/latest/config/#set-of-immutable-commits
+          - `jj help -k config`, "Set of immutable commits"
+    Hint: This operation would rewrite 1 immutable commits.
+    [EOF]
+    [exit status: 1]
+    "#);
+    // The kept commit is rewritten by --merge
+    let output = work_dir.run_jj([
+        "resolve-divergence",
+        "-r=all()",
+        "--keep=subject('a one')",
+        "--merge",
+    ]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: Commit de277eaeb26b is immutable
+    Hint: Could not modify commit: rlvkpnrz/1 de277eae a?? | (divergent) a one
+    Hint: Immutable commits are used to protect shared history.
+    Hint: For more information, see:
+          - https://docs.jj-vcs.dev/latest/config/#set-of-immutable-commits
+          - `jj help -k config`, "Set of immutable commits"
+    Hint: This operation would rewrite 1 immutable commits.
+    [EOF]
+    [exit status: 1]
+    "#);
+}
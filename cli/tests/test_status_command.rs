@@ -122,6 +122,89 @@ fn test_status_filtered() {
     ");
 }
 
+#[test]
+fn test_status_short() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"snapshot.auto-track = "~untracked*""#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("modified", "a");
+    work_dir.write_file("deleted", "deleted");
+    work_dir.write_file("renamed", "a\nb\nc\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("modified", "b");
+    work_dir.remove_file("deleted");
+    work_dir.remove_file("renamed");
+    work_dir.write_file("renamed-to", "a\nb\nc\n");
+    work_dir.write_file("added", "added");
+    work_dir.write_file("with \"quotes\" and space", "added");
+    work_dir.write_file("untracked1", "a");
+    work_dir.write_file("untracked2", "a");
+
+    let output = work_dir.run_jj(["status", "--short"]);
+    insta::assert_snapshot!(output, @r#"
+    A added
+    D deleted
+    M modified
+    R renamed -> renamed-to
+    A "with \"quotes\" and space"
+    ? untracked1
+    ? untracked2
+    [EOF]
+    "#);
+
+    // Untracked paths are filtered too
+    let output = work_dir.run_jj(["status", "-s", "modified", "untracked2"]);
+    insta::assert_snapshot!(output, @"
+    M modified
+    ? untracked2
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["status", "untracked1"]);
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    Untracked paths:
+    ? untracked1
+    Working copy  (@) : rlvkpnrz eabe6596 (no description set)
+    Parent commit (@-): qpvuntsm fff6f38d (no description set)
+    [EOF]
+    ");
+
+    // No output if there are no changes
+    let output = work_dir.run_jj(["status", "-s", "nonexistent"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No matching entries for paths: nonexistent
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_status_short_conflict() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "base\n");
+    work_dir.run_jj(["new", "-m=a"]).success();
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["new", "@-", "-m=b"]).success();
+    work_dir.write_file("file", "b\n");
+    work_dir
+        .run_jj(["new", "subject(a)", "subject(b)"])
+        .success();
+    work_dir.write_file("other", "new\n");
+
+    // Conflicted files are listed even if they're unchanged from the parents
+    let output = work_dir.run_jj(["status", "--short"]);
+    insta::assert_snapshot!(output, @r"
+    A other
+    U file
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_status_conflicted_bookmarks() {
     // create conflicted local bookmark
@@ -1263,6 +1263,81 @@ fn test_workspaces_forget_nothing_changed() {
     ");
 }
 
+#[test]
+fn test_workspaces_forget_abandon_and_delete() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    main_dir.write_file("file", "contents");
+    main_dir.run_jj(["new"]).success();
+    main_dir
+        .run_jj(["workspace", "add", "../secondary"])
+        .success();
+    let secondary_dir = test_env.work_dir("secondary");
+    secondary_dir.write_file("file", "changed");
+
+    // Can't delete another workspace, or the workspace containing the repo
+    let output = main_dir.run_jj(["workspace", "forget", "--delete", "secondary"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: --delete can only be used when forgetting the current workspace
+    Hint: Run the command from the workspace to delete.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = main_dir.run_jj(["workspace", "forget", "--delete"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot delete the workspace that contains the repo
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Deleting the directory needs confirmation, which is declined when not
+    // running interactively
+    secondary_dir.write_file(".gitignore", "ignored\n");
+    secondary_dir.write_file("ignored", "precious");
+    let output = secondary_dir.run_jj(["workspace", "forget", "--abandon", "--delete"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    ------- stderr -------
+    Delete $TEST_ENV/secondary and everything in it, including ignored and untracked files? (yN): n
+    Error: Workspace was not forgotten
+    Hint: Pass --force to delete the workspace directory without confirmation.
+    [EOF]
+    [exit status: 1]
+    ");
+    assert!(test_env.env_root().join("secondary/ignored").exists());
+    let output = main_dir.run_jj(["workspace", "list"]);
+    insta::assert_snapshot!(output, @r"
+    default: rlvkpnrz f6bf8819 (empty) (no description set)
+    secondary: pmmvwywv cf4339cc (no description set)
+    [EOF]
+    ");
+
+    // The working-copy commit with changes is abandoned, and the directory is
+    // deleted
+    let output = secondary_dir.run_jj([
+        "workspace",
+        "forget",
+        "--abandon",
+        "--delete",
+        "--force",
+    ]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    ------- stderr -------
+    Abandoned 1 working-copy commits
+    Deleted workspace directory $TEST_ENV/secondary
+    [EOF]
+    ");
+    assert!(!test_env.env_root().join("secondary").exists());
+    insta::assert_snapshot!(get_log_output(&main_dir), @r"
+    @  f6bf88196496
+    ○  006bd1130b84
+    ◆  000000000000
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_workspaces_forget_multi_transaction() {
     let test_env = TestEnvironment::default();
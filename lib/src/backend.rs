@@ -22,6 +22,7 @@ use std::time::SystemTime;
 
 use async_trait::async_trait;
 use chrono::TimeZone as _;
+use futures::future::try_join_all;
 use futures::stream::BoxStream;
 use thiserror::Error;
 use tokio::io::AsyncRead;
@@ -482,6 +483,15 @@ pub trait Backend: Any + Send + Sync + Debug {
 
     async fn read_commit(&self, id: &CommitId) -> BackendResult<Commit>;
 
+    /// Reads multiple commits, returned in the same order as `ids`.
+    ///
+    /// The default implementation issues one `read_commit()` request per
+    /// commit. Backends that can amortize IO or RPC round trips may override
+    /// this.
+    async fn read_commits(&self, ids: &[CommitId]) -> BackendResult<Vec<Commit>> {
+        try_join_all(ids.iter().map(|id| self.read_commit(id))).await
+    }
+
     /// Writes a commit and returns its ID and the commit itself. The commit
     /// should contain the data that was actually written, which may differ
     /// from the data passed in. For example, the backend may change the
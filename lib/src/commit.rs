@@ -22,7 +22,6 @@ use std::hash::Hash;
 use std::hash::Hasher;
 use std::sync::Arc;
 
-use futures::future::try_join_all;
 use itertools::Itertools as _;
 use pollster::FutureExt as _;
 
@@ -112,13 +111,7 @@ impl Commit {
     }
 
     pub async fn parents_async(&self) -> BackendResult<Vec<Self>> {
-        try_join_all(
-            self.data
-                .parents
-                .iter()
-                .map(|id| self.store.get_commit_async(id)),
-        )
-        .await
+        self.store.get_commits_async(&self.data.parents).await
     }
 
     pub fn tree(&self) -> MergedTree {
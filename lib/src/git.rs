@@ -23,8 +23,10 @@ use std::ffi::OsString;
 use std::fs::File;
 use std::iter;
 use std::num::NonZeroU32;
+use std::panic;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::thread;
 
 use bstr::BStr;
 use bstr::BString;
@@ -40,6 +42,7 @@ use crate::backend::CommitId;
 use crate::backend::TreeValue;
 use crate::commit::Commit;
 use crate::config::ConfigGetError;
+use crate::file_util;
 use crate::file_util::IoResultExt as _;
 use crate::file_util::PathError;
 use crate::git_backend::GitBackend;
@@ -91,6 +94,12 @@ pub struct GitSettings {
     pub abandon_unreachable_commits: bool,
     pub executable_path: PathBuf,
     pub write_change_id_header: bool,
+    /// Maximum number of source and destination pairs compared by similarity
+    /// for rename and copy detection. `0` means unlimited.
+    pub rename_limit: usize,
+    /// Minimum similarity (in percent) for a file to be detected as renamed or
+    /// copied.
+    pub rename_similarity: u8,
 }
 
 impl GitSettings {
@@ -98,8 +107,12 @@ impl GitSettings {
         Ok(Self {
             auto_local_bookmark: settings.get_bool("git.auto-local-bookmark")?,
             abandon_unreachable_commits: settings.get_bool("git.abandon-unreachable-commits")?,
-            executable_path: settings.get("git.executable-path")?,
+            executable_path: file_util::expand_home_path(
+                &settings.get_string("git.executable-path")?,
+            ),
             write_change_id_header: settings.get("git.write-change-id-header")?,
+            rename_limit: settings.get("git.rename-limit")?,
+            rename_similarity: settings.get("git.rename-similarity")?,
         })
     }
 
@@ -2641,62 +2654,30 @@ impl<'a> GitFetch<'a> {
     pub fn fetch(
         &mut self,
         remote_name: &RemoteName,
-        ExpandedFetchRefSpecs {
-            bookmark_expr,
-            refspecs: mut remaining_refspecs,
-            negative_refspecs,
-        }: ExpandedFetchRefSpecs,
+        expanded_refspecs: ExpandedFetchRefSpecs,
         mut callbacks: RemoteCallbacks,
         depth: Option<NonZeroU32>,
         fetch_tags_override: Option<FetchTagsOverride>,
     ) -> Result<(), GitFetchError> {
-        validate_remote_name(remote_name)?;
-
-        // check the remote exists
-        if self
-            .git_repo
-            .try_find_remote(remote_name.as_str())
-            .is_none()
-        {
-            return Err(GitFetchError::NoSuchRemote(remote_name.to_owned()));
-        }
-
-        if remaining_refspecs.is_empty() {
+        self.check_remote_exists(remote_name)?;
+        let ExpandedFetchRefSpecs {
+            bookmark_expr,
+            refspecs,
+            negative_refspecs,
+        } = expanded_refspecs;
+        if refspecs.is_empty() {
             // Don't fall back to the base refspecs.
             return Ok(());
         }
-
-        let mut branches_to_prune = Vec::new();
-        // git unfortunately errors out if one of the many refspecs is not found
-        //
-        // our approach is to filter out failures and retry,
-        // until either all have failed or an attempt has succeeded
-        //
-        // even more unfortunately, git errors out one refspec at a time,
-        // meaning that the below cycle runs in O(#failed refspecs)
-        while let Some(failing_refspec) = self.git_ctx.spawn_fetch(
+        fetch_from_remote(
+            &self.git_ctx,
             remote_name,
-            &remaining_refspecs,
+            refspecs,
             &negative_refspecs,
             &mut callbacks,
             depth,
             fetch_tags_override,
-        )? {
-            tracing::debug!(failing_refspec, "failed to fetch ref");
-            remaining_refspecs.retain(|r| r.source.as_ref() != Some(&failing_refspec));
-
-            if let Some(branch_name) = failing_refspec.strip_prefix("refs/heads/") {
-                branches_to_prune.push(format!(
-                    "{remote_name}/{branch_name}",
-                    remote_name = remote_name.as_str()
-                ));
-            }
-        }
-
-        // Even if git fetch has --prune, if a branch is not found it will not be
-        // pruned on fetch
-        self.git_ctx.spawn_branch_prune(&branches_to_prune)?;
-
+        )?;
         self.fetched.push(FetchedBranches {
             remote: remote_name.to_owned(),
             bookmark_matcher: bookmark_expr.to_matcher(),
@@ -2704,6 +2685,112 @@ impl<'a> GitFetch<'a> {
         Ok(())
     }
 
+    /// Like `fetch()`, but fetches from several remotes concurrently.
+    ///
+    /// Progress isn't reported since the transfers of different remotes are
+    /// interleaved. Sideband messages are buffered per remote, and emitted in
+    /// `fetches` order once all transfers have completed. If some of the
+    /// fetches fail, the error of the first failed remote in `fetches` order
+    /// is returned.
+    #[tracing::instrument(skip_all)]
+    pub fn fetch_concurrently(
+        &mut self,
+        fetches: Vec<(&RemoteName, ExpandedFetchRefSpecs)>,
+        mut callbacks: RemoteCallbacks,
+        depth: Option<NonZeroU32>,
+        fetch_tags_override: Option<FetchTagsOverride>,
+    ) -> Result<(), GitFetchError> {
+        let mut jobs = Vec::with_capacity(fetches.len());
+        let mut fetched = Vec::with_capacity(fetches.len());
+        for (remote_name, expanded_refspecs) in fetches {
+            self.check_remote_exists(remote_name)?;
+            let ExpandedFetchRefSpecs {
+                bookmark_expr,
+                refspecs,
+                negative_refspecs,
+            } = expanded_refspecs;
+            if refspecs.is_empty() {
+                // Don't fall back to the base refspecs.
+                continue;
+            }
+            jobs.push((remote_name, refspecs, negative_refspecs));
+            fetched.push(FetchedBranches {
+                remote: remote_name.to_owned(),
+                bookmark_matcher: bookmark_expr.to_matcher(),
+            });
+        }
+        let git_ctx = &self.git_ctx;
+        let results = thread::scope(|s| {
+            let handles = jobs
+                .into_iter()
+                .map(|(remote_name, refspecs, negative_refspecs)| {
+                    s.spawn(move || {
+                        let mut sideband = Vec::new();
+                        let mut sideband_cb = |message: &[u8]| sideband.push(message.to_owned());
+                        let mut callbacks = RemoteCallbacks {
+                            sideband_progress: Some(&mut sideband_cb),
+                            ..Default::default()
+                        };
+                        let result = fetch_without_branch_prune(
+                            git_ctx,
+                            remote_name,
+                            refspecs,
+                            &negative_refspecs,
+                            &mut callbacks,
+                            depth,
+                            fetch_tags_override,
+                        );
+                        (result, sideband)
+                    })
+                })
+                .collect_vec();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|err| panic::resume_unwind(err))
+                })
+                .collect_vec()
+        });
+        let mut branches_to_prune = Vec::new();
+        let mut first_error = None;
+        for (result, sideband) in results {
+            if let Some(cb) = callbacks.sideband_progress.as_mut() {
+                for message in &sideband {
+                    cb(message);
+                }
+            }
+            match result {
+                Ok(branches) => branches_to_prune.extend(branches),
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+        if let Some(err) = first_error {
+            return Err(err);
+        }
+        // Prune after all fetches have completed. Concurrent `git fetch
+        // --prune` and `git branch --delete` processes would race for the
+        // packed-refs lock.
+        self.git_ctx.spawn_branch_prune(&branches_to_prune)?;
+        self.fetched.extend(fetched);
+        Ok(())
+    }
+
+    fn check_remote_exists(&self, remote_name: &RemoteName) -> Result<(), GitFetchError> {
+        validate_remote_name(remote_name)?;
+        if self
+            .git_repo
+            .try_find_remote(remote_name.as_str())
+            .is_none()
+        {
+            return Err(GitFetchError::NoSuchRemote(remote_name.to_owned()));
+        }
+        Ok(())
+    }
+
     /// Queries remote for the default branch name.
     #[tracing::instrument(skip(self))]
     pub fn get_default_branch(
@@ -2752,6 +2839,73 @@ impl<'a> GitFetch<'a> {
     }
 }
 
+/// Runs `git fetch` for the given remote, retrying without the refspecs that
+/// couldn't be found.
+fn fetch_from_remote(
+    git_ctx: &GitSubprocessContext,
+    remote_name: &RemoteName,
+    remaining_refspecs: Vec<RefSpec>,
+    negative_refspecs: &[NegativeRefSpec],
+    callbacks: &mut RemoteCallbacks,
+    depth: Option<NonZeroU32>,
+    fetch_tags_override: Option<FetchTagsOverride>,
+) -> Result<(), GitFetchError> {
+    let branches_to_prune = fetch_without_branch_prune(
+        git_ctx,
+        remote_name,
+        remaining_refspecs,
+        negative_refspecs,
+        callbacks,
+        depth,
+        fetch_tags_override,
+    )?;
+    // Even if git fetch has --prune, if a branch is not found it will not be
+    // pruned on fetch
+    git_ctx.spawn_branch_prune(&branches_to_prune)?;
+    Ok(())
+}
+
+/// Like `fetch_from_remote()`, but returns the remote-tracking branches to
+/// be pruned instead of deleting them.
+fn fetch_without_branch_prune(
+    git_ctx: &GitSubprocessContext,
+    remote_name: &RemoteName,
+    mut remaining_refspecs: Vec<RefSpec>,
+    negative_refspecs: &[NegativeRefSpec],
+    callbacks: &mut RemoteCallbacks,
+    depth: Option<NonZeroU32>,
+    fetch_tags_override: Option<FetchTagsOverride>,
+) -> Result<Vec<String>, GitFetchError> {
+    let mut branches_to_prune = Vec::new();
+    // git unfortunately errors out if one of the many refspecs is not found
+    //
+    // our approach is to filter out failures and retry,
+    // until either all have failed or an attempt has succeeded
+    //
+    // even more unfortunately, git errors out one refspec at a time,
+    // meaning that the below cycle runs in O(#failed refspecs)
+    while let Some(failing_refspec) = git_ctx.spawn_fetch(
+        remote_name,
+        &remaining_refspecs,
+        negative_refspecs,
+        callbacks,
+        depth,
+        fetch_tags_override,
+    )? {
+        tracing::debug!(failing_refspec, "failed to fetch ref");
+        remaining_refspecs.retain(|r| r.source.as_ref() != Some(&failing_refspec));
+
+        if let Some(branch_name) = failing_refspec.strip_prefix("refs/heads/") {
+            branches_to_prune.push(format!(
+                "{remote_name}/{branch_name}",
+                remote_name = remote_name.as_str()
+            ));
+        }
+    }
+
+    Ok(branches_to_prune)
+}
+
 #[derive(Error, Debug)]
 pub enum GitPushError {
     #[error("No git remote named '{}'", .0.as_symbol())]
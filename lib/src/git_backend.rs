@@ -34,6 +34,7 @@ use std::sync::MutexGuard;
 use std::time::SystemTime;
 
 use async_trait::async_trait;
+use clru::CLruCache;
 use futures::stream::BoxStream;
 use gix::bstr::BString;
 use gix::objs::CommitRefIter;
@@ -99,6 +100,11 @@ pub const JJ_TREES_COMMIT_HEADER: &str = "jj:trees";
 pub const JJ_CONFLICT_LABELS_COMMIT_HEADER: &str = "jj:conflict-labels";
 pub const CHANGE_ID_COMMIT_HEADER: &str = "change-id";
 
+const COPY_RECORDS_CACHE_CAPACITY: usize = 100;
+
+/// Copy records detected between pairs of (root, head) commits.
+type CopyRecordsCache = CLruCache<(CommitId, CommitId), Arc<[CopyRecord]>>;
+
 #[derive(Debug, Error)]
 pub enum GitBackendInitError {
     #[error("Failed to initialize git repository")]
@@ -175,6 +181,9 @@ pub struct GitBackend {
     cached_extra_metadata: Mutex<Option<Arc<ReadonlyTable>>>,
     git_executable: PathBuf,
     write_change_id_header: bool,
+    rename_limit: usize,
+    rename_similarity: f32,
+    copy_records_cache: Mutex<CopyRecordsCache>,
 }
 
 impl GitBackend {
@@ -202,6 +211,11 @@ impl GitBackend {
             cached_extra_metadata: Mutex::new(None),
             git_executable: git_settings.executable_path,
             write_change_id_header: git_settings.write_change_id_header,
+            rename_limit: git_settings.rename_limit,
+            rename_similarity: f32::from(git_settings.rename_similarity.min(100)) / 100.0,
+            copy_records_cache: Mutex::new(CLruCache::new(
+                COPY_RECORDS_CACHE_CAPACITY.try_into().unwrap(),
+            )),
         }
     }
 
@@ -328,6 +342,80 @@ impl GitBackend {
         Ok(Self::new(repo, extra_metadata_store, git_settings))
     }
 
+    /// Detects copies and renames between the trees of the given commits.
+    fn detect_copies(
+        &self,
+        root_id: &CommitId,
+        head_id: &CommitId,
+    ) -> BackendResult<Vec<BackendResult<CopyRecord>>> {
+        let repo = self.git_repo();
+        let root_tree = self.read_tree_for_commit(&repo, root_id)?;
+        let head_tree = self.read_tree_for_commit(&repo, head_id)?;
+
+        let change_to_copy_record =
+            |change: gix::object::tree::diff::Change| -> BackendResult<Option<CopyRecord>> {
+                let gix::object::tree::diff::Change::Rewrite {
+                    source_location,
+                    source_entry_mode,
+                    source_id,
+                    entry_mode: dest_entry_mode,
+                    location: dest_location,
+                    ..
+                } = change
+                else {
+                    return Ok(None);
+                };
+                // TODO: Renamed symlinks cannot be returned because CopyRecord
+                // expects `source_file: FileId`.
+                if !source_entry_mode.is_blob() || !dest_entry_mode.is_blob() {
+                    return Ok(None);
+                }
+
+                let source = str::from_utf8(source_location)
+                    .map_err(|err| to_invalid_utf8_err(err, root_id))?;
+                let dest = str::from_utf8(dest_location)
+                    .map_err(|err| to_invalid_utf8_err(err, head_id))?;
+
+                Ok(Some(CopyRecord {
+                    target: RepoPathBuf::from_internal_string(dest).unwrap(),
+                    target_commit: head_id.clone(),
+                    source: RepoPathBuf::from_internal_string(source).unwrap(),
+                    source_file: FileId::from_bytes(source_id.as_bytes()),
+                    source_commit: root_id.clone(),
+                }))
+            };
+
+        let mut records: Vec<BackendResult<CopyRecord>> = Vec::new();
+        root_tree
+            .changes()
+            .map_err(|err| BackendError::Other(err.into()))?
+            .options(|opts| {
+                opts.track_path().track_rewrites(Some(gix::diff::Rewrites {
+                    copies: Some(gix::diff::rewrites::Copies {
+                        source: gix::diff::rewrites::CopySource::FromSetOfModifiedFiles,
+                        percentage: Some(self.rename_similarity),
+                    }),
+                    percentage: Some(self.rename_similarity),
+                    limit: self.rename_limit,
+                    track_empty: false,
+                }));
+            })
+            .for_each_to_obtain_tree_with_cache(
+                &head_tree,
+                &mut self.new_diff_platform()?,
+                |change| -> BackendResult<_> {
+                    match change_to_copy_record(change) {
+                        Ok(None) => {}
+                        Ok(Some(change)) => records.push(Ok(change)),
+                        Err(err) => records.push(Err(err)),
+                    }
+                    Ok(gix::object::tree::diff::Action::Continue)
+                },
+            )
+            .map_err(|err| BackendError::Other(err.into()))?;
+        Ok(records)
+    }
+
     fn lock_git_repo(&self) -> MutexGuard<'_, gix::Repository> {
         self.repo.lock().unwrap()
     }
@@ -1380,76 +1468,39 @@ impl Backend for GitBackend {
         root_id: &CommitId,
         head_id: &CommitId,
     ) -> BackendResult<BoxStream<'_, BackendResult<CopyRecord>>> {
-        let repo = self.git_repo();
-        let root_tree = self.read_tree_for_commit(&repo, root_id)?;
-        let head_tree = self.read_tree_for_commit(&repo, head_id)?;
-
-        let change_to_copy_record =
-            |change: gix::object::tree::diff::Change| -> BackendResult<Option<CopyRecord>> {
-                let gix::object::tree::diff::Change::Rewrite {
-                    source_location,
-                    source_entry_mode,
-                    source_id,
-                    entry_mode: dest_entry_mode,
-                    location: dest_location,
-                    ..
-                } = change
-                else {
-                    return Ok(None);
-                };
-                // TODO: Renamed symlinks cannot be returned because CopyRecord
-                // expects `source_file: FileId`.
-                if !source_entry_mode.is_blob() || !dest_entry_mode.is_blob() {
-                    return Ok(None);
-                }
-
-                let source = str::from_utf8(source_location)
-                    .map_err(|err| to_invalid_utf8_err(err, root_id))?;
-                let dest = str::from_utf8(dest_location)
-                    .map_err(|err| to_invalid_utf8_err(err, head_id))?;
-
-                let target = RepoPathBuf::from_internal_string(dest).unwrap();
-                if !paths.is_none_or(|paths| paths.contains(&target)) {
-                    return Ok(None);
-                }
-
-                Ok(Some(CopyRecord {
-                    target,
-                    target_commit: head_id.clone(),
-                    source: RepoPathBuf::from_internal_string(source).unwrap(),
-                    source_file: FileId::from_bytes(source_id.as_bytes()),
-                    source_commit: root_id.clone(),
-                }))
-            };
-
-        let mut records: Vec<BackendResult<CopyRecord>> = Vec::new();
-        root_tree
-            .changes()
-            .map_err(|err| BackendError::Other(err.into()))?
-            .options(|opts| {
-                opts.track_path().track_rewrites(Some(gix::diff::Rewrites {
-                    copies: Some(gix::diff::rewrites::Copies {
-                        source: gix::diff::rewrites::CopySource::FromSetOfModifiedFiles,
-                        percentage: Some(0.5),
-                    }),
-                    percentage: Some(0.5),
-                    limit: 1000,
-                    track_empty: false,
-                }));
+        let cache_key = (root_id.clone(), head_id.clone());
+        let cached = self
+            .copy_records_cache
+            .lock()
+            .unwrap()
+            .get(&cache_key)
+            .cloned();
+        let records = if let Some(all_records) = cached {
+            all_records.iter().cloned().map(Ok).collect_vec()
+        } else {
+            let records = self.detect_copies(root_id, head_id)?;
+            // Records with errors aren't cached so the errors will be reported
+            // again next time.
+            if records.iter().all(|record| record.is_ok()) {
+                let all_records = records
+                    .iter()
+                    .map(|record| record.as_ref().unwrap().clone())
+                    .collect();
+                self.copy_records_cache
+                    .lock()
+                    .unwrap()
+                    .put(cache_key, all_records);
+            }
+            records
+        };
+        let records = records
+            .into_iter()
+            .filter(|record| {
+                record
+                    .as_ref()
+                    .map_or(true, |record| paths.is_none_or(|paths| paths.contains(&record.target)))
             })
-            .for_each_to_obtain_tree_with_cache(
-                &head_tree,
-                &mut self.new_diff_platform()?,
-                |change| -> BackendResult<_> {
-                    match change_to_copy_record(change) {
-                        Ok(None) => {}
-                        Ok(Some(change)) => records.push(Ok(change)),
-                        Err(err) => records.push(Err(err)),
-                    }
-                    Ok(gix::object::tree::diff::Action::Continue)
-                },
-            )
-            .map_err(|err| BackendError::Other(err.into()))?;
+            .collect_vec();
         Ok(Box::pin(futures::stream::iter(records)))
     }
 
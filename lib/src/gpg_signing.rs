@@ -25,6 +25,7 @@ use std::process::Stdio;
 use thiserror::Error;
 
 use crate::config::ConfigGetError;
+use crate::file_util::expand_home_path;
 use crate::settings::UserSettings;
 use crate::signing::SigStatus;
 use crate::signing::SignError;
@@ -154,7 +155,11 @@ impl GpgBackend {
         let program = settings.get_string("signing.backends.gpg.program")?;
         let allow_expired_keys = settings.get_bool("signing.backends.gpg.allow-expired-keys")?;
         let default_key = settings.user_email().to_owned();
-        Ok(Self::new(program.into(), allow_expired_keys, default_key))
+        Ok(Self::new(
+            expand_home_path(&program).into(),
+            allow_expired_keys,
+            default_key,
+        ))
     }
 
     fn create_command(&self) -> Command {
@@ -242,7 +247,11 @@ impl GpgsmBackend {
         let program = settings.get_string("signing.backends.gpgsm.program")?;
         let allow_expired_keys = settings.get_bool("signing.backends.gpgsm.allow-expired-keys")?;
         let default_key = settings.user_email().to_owned();
-        Ok(Self::new(program.into(), allow_expired_keys, default_key))
+        Ok(Self::new(
+            expand_home_path(&program).into(),
+            allow_expired_keys,
+            default_key,
+        ))
     }
 
     fn create_command(&self) -> Command {
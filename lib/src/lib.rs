@@ -78,6 +78,7 @@ pub mod op_heads_store;
 pub mod op_store;
 pub mod op_walk;
 pub mod operation;
+pub mod patch;
 #[expect(missing_docs)]
 pub mod protos;
 pub mod ref_name;
@@ -0,0 +1,1330 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing and applying patches in the unified diff format.
+//!
+//! Both plain unified diffs and Git-style patches (as produced by `git diff`,
+//! `git format-patch`, or `jj diff --git`) are supported. Binary patches are
+//! recognized but can't be applied.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use bstr::BString;
+use bstr::ByteSlice as _;
+use itertools::Itertools as _;
+use thiserror::Error;
+use tokio::io::AsyncReadExt as _;
+
+use crate::backend::BackendError;
+use crate::backend::BackendResult;
+use crate::backend::CopyId;
+use crate::backend::Timestamp;
+use crate::backend::TreeValue;
+use crate::merge::Merge;
+use crate::merge::MergedTreeValue;
+use crate::merged_tree::MergedTree;
+use crate::merged_tree::MergedTreeBuilder;
+use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
+
+/// Error that may occur while parsing a patch.
+#[expect(missing_docs)]
+#[derive(Debug, Error)]
+pub enum PatchParseError {
+    #[error("Invalid hunk header: {line}")]
+    InvalidHunkHeader { line: BString },
+    #[error("Unexpected line in hunk: {line}")]
+    InvalidHunkLine { line: BString },
+    #[error("Patch ended in the middle of a hunk")]
+    TruncatedHunk,
+    #[error("Hunk found before file header: {line}")]
+    HunkWithoutFile { line: BString },
+    #[error("Invalid path in patch: {path}")]
+    InvalidPath { path: BString },
+    #[error("File in patch has neither old nor new path")]
+    MissingPath,
+    #[error("Invalid file mode in patch: {mode}")]
+    InvalidMode { mode: BString },
+}
+
+/// Error that may occur while applying a patch to a tree.
+#[expect(missing_docs)]
+#[derive(Debug, Error)]
+pub enum PatchApplyError {
+    #[error("Hunk #{index} does not apply to {}", path.as_internal_file_string())]
+    HunkMismatch { path: RepoPathBuf, index: usize },
+    #[error("File {} does not exist", path.as_internal_file_string())]
+    MissingFile { path: RepoPathBuf },
+    #[error("File {} already exists", path.as_internal_file_string())]
+    FileExists { path: RepoPathBuf },
+    #[error("File {} is conflicted or not a regular file", path.as_internal_file_string())]
+    UnsupportedFile { path: RepoPathBuf },
+    #[error("Binary patch for {} is not supported", path.as_internal_file_string())]
+    BinaryPatch { path: RepoPathBuf },
+    #[error("Symlink target of {} is not valid UTF-8", path.as_internal_file_string())]
+    InvalidSymlink { path: RepoPathBuf },
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+}
+
+/// Parsed patch, possibly with metadata from email-style headers.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Patch {
+    /// Author name and email from the `From:` header.
+    pub author: Option<(String, String)>,
+    /// Author date from the `Date:` header.
+    pub author_timestamp: Option<Timestamp>,
+    /// Commit message made of the `Subject:` header and the message body. Empty
+    /// if the patch has no headers.
+    pub description: String,
+    /// Changes to individual files.
+    pub files: Vec<FilePatch>,
+}
+
+/// Mode of a file in a patch.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FileMode {
+    /// Regular file (`100644`).
+    Normal,
+    /// Executable file (`100755`).
+    Executable,
+    /// Symbolic link (`120000`).
+    Symlink,
+}
+
+impl FileMode {
+    fn parse(mode: &[u8]) -> Result<Self, PatchParseError> {
+        match mode {
+            b"100644" | b"100664" => Ok(Self::Normal),
+            b"100755" => Ok(Self::Executable),
+            b"120000" => Ok(Self::Symlink),
+            _ => Err(PatchParseError::InvalidMode { mode: mode.into() }),
+        }
+    }
+}
+
+/// Changes to a single file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FilePatch {
+    /// Path before the change, or `None` if the file is added.
+    pub old_path: Option<RepoPathBuf>,
+    /// Path after the change, or `None` if the file is deleted.
+    pub new_path: Option<RepoPathBuf>,
+    /// File mode before the change, if known.
+    pub old_mode: Option<FileMode>,
+    /// File mode after the change, if known.
+    pub new_mode: Option<FileMode>,
+    /// Whether the old file is kept when the paths differ.
+    pub is_copy: bool,
+    /// Whether the patch contains binary changes.
+    pub is_binary: bool,
+    /// Content changes.
+    pub hunks: Vec<Hunk>,
+}
+
+impl FilePatch {
+    /// Returns the path after the change, or the path before the change if the
+    /// file is deleted.
+    pub fn path(&self) -> Option<&RepoPath> {
+        self.new_path.as_deref().or(self.old_path.as_deref())
+    }
+}
+
+/// Contiguous region of changes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Hunk {
+    /// 1-based line number of the first line in the old file.
+    pub old_start: usize,
+    /// 1-based line number of the first line in the new file.
+    pub new_start: usize,
+    /// Lines of the hunk, including line terminators.
+    pub lines: Vec<HunkLine>,
+}
+
+/// Line in a hunk.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HunkLine {
+    /// Line present in both the old and the new file.
+    Context(BString),
+    /// Line present only in the old file.
+    Removed(BString),
+    /// Line present only in the new file.
+    Added(BString),
+}
+
+impl Hunk {
+    fn old_lines(&self) -> impl Iterator<Item = &[u8]> {
+        self.lines.iter().filter_map(|line| match line {
+            HunkLine::Context(text) | HunkLine::Removed(text) => Some(text.as_slice()),
+            HunkLine::Added(_) => None,
+        })
+    }
+
+    fn new_lines(&self) -> impl Iterator<Item = &[u8]> {
+        self.lines.iter().filter_map(|line| match line {
+            HunkLine::Context(text) | HunkLine::Added(text) => Some(text.as_slice()),
+            HunkLine::Removed(_) => None,
+        })
+    }
+}
+
+/// Parses a patch containing changes to any number of files.
+///
+/// Email-style headers (as produced by `git format-patch`) before the diff
+/// are used to fill the author and description of the patch.
+pub fn parse_patch(input: &[u8]) -> Result<Patch, PatchParseError> {
+    let mut lines = input.lines_with_terminator().peekable();
+    let mut patch = Patch::default();
+    parse_headers(&mut lines, &mut patch);
+
+    let mut current: Option<FilePatch> = None;
+    while let Some(line) = lines.next() {
+        if let Some(rest) = line.strip_prefix(b"diff --git ") {
+            push_file(&mut patch.files, current.take())?;
+            let mut file = FilePatch::default();
+            if let Some((old, new)) = split_git_diff_paths(trim_eol(rest)) {
+                file.old_path = Some(parse_path(&old, 1)?);
+                file.new_path = Some(parse_path(&new, 1)?);
+            }
+            current = Some(file);
+        } else if line.starts_with(b"--- ")
+            && lines.peek().is_some_and(|next| next.starts_with(b"+++ "))
+        {
+            let new_line = lines.next().unwrap();
+            let file = match &mut current {
+                Some(file) if file.hunks.is_empty() => file,
+                _ => {
+                    push_file(&mut patch.files, current.take())?;
+                    current.insert(FilePatch::default())
+                }
+            };
+            let old = parse_file_header_path(&line[4..])?;
+            let new = parse_file_header_path(&new_line[4..])?;
+            if old.is_none() {
+                file.old_path = None;
+            } else if file.old_path.is_none() || file.new_path.is_none() {
+                file.old_path = old;
+            }
+            if new.is_none() {
+                file.new_path = None;
+            } else if file.new_path.is_none() || file.old_path.is_none() {
+                file.new_path = new;
+            }
+        } else if line.starts_with(b"@@ ") {
+            let Some(file) = &mut current else {
+                return Err(PatchParseError::HunkWithoutFile { line: line.into() });
+            };
+            file.hunks.push(parse_hunk(line, &mut lines)?);
+        } else if let Some(file) = &mut current
+            && file.hunks.is_empty()
+        {
+            parse_extended_header(trim_eol(line), file)?;
+        }
+    }
+    push_file(&mut patch.files, current)?;
+    Ok(patch)
+}
+
+fn push_file(files: &mut Vec<FilePatch>, file: Option<FilePatch>) -> Result<(), PatchParseError> {
+    if let Some(file) = file {
+        if file.path().is_none() {
+            return Err(PatchParseError::MissingPath);
+        }
+        files.push(file);
+    }
+    Ok(())
+}
+
+/// Parses a series of patches, such as an mbox file produced by
+/// `git format-patch --stdout`.
+///
+/// The input is split into messages at the `From <commit> Mon Sep 17 00:00:00
+/// 2001` separator lines written by `git format-patch`. Input without such
+/// separators is parsed as a single patch.
+pub fn parse_patch_series(input: &[u8]) -> Result<Vec<Patch>, PatchParseError> {
+    split_mbox(input).into_iter().map(parse_patch).collect()
+}
+
+fn split_mbox(input: &[u8]) -> Vec<&[u8]> {
+    let mut messages = vec![];
+    let mut start = 0;
+    let mut offset = 0;
+    for line in input.lines_with_terminator() {
+        if is_mbox_separator(line) && offset > start {
+            messages.push(&input[start..offset]);
+            start = offset;
+        }
+        offset += line.len();
+    }
+    messages.push(&input[start..]);
+    messages
+}
+
+/// Returns true if the line is the "From <commit> <magic date>" line that
+/// starts each message written by `git format-patch`.
+fn is_mbox_separator(line: &[u8]) -> bool {
+    trim_eol(line)
+        .strip_prefix(b"From ")
+        .and_then(|rest| rest.split_once_str(" "))
+        .is_some_and(|(id, date)| {
+            id.len() == 40 && id.iter().all(u8::is_ascii_hexdigit) && date == MBOX_MAGIC_DATE
+        })
+}
+
+const MBOX_MAGIC_DATE: &[u8] = b"Mon Sep 17 00:00:00 2001";
+
+fn parse_headers<'a>(
+    lines: &mut std::iter::Peekable<impl Iterator<Item = &'a [u8]>>,
+    patch: &mut Patch,
+) {
+    // mbox "From <commit> <date>" separator line
+    if lines.peek().is_some_and(|line| line.starts_with(b"From ")) {
+        lines.next();
+    }
+    let mut subject = None;
+    let mut has_headers = false;
+    while let Some(line) = lines.peek() {
+        let line = trim_eol(line);
+        let Some((name, value)) = line.split_once_str(":") else {
+            break;
+        };
+        if name.is_empty() || name.contains(&b' ') {
+            break;
+        }
+        lines.next();
+        has_headers = true;
+        let mut value = value.trim().to_str_lossy().into_owned();
+        // Folded header continuation lines
+        while let Some(next) = lines.peek()
+            && (next.starts_with(b" ") || next.starts_with(b"\t"))
+        {
+            value.push(' ');
+            value.push_str(&trim_eol(next).trim().to_str_lossy());
+            lines.next();
+        }
+        match name.to_ascii_lowercase().as_slice() {
+            b"from" => patch.author = parse_author(&decode_header_value(&value)),
+            b"date" => {
+                patch.author_timestamp = chrono::DateTime::parse_from_rfc2822(&value)
+                    .ok()
+                    .map(Timestamp::from_datetime);
+            }
+            b"subject" => {
+                subject = Some(strip_patch_prefix(&decode_header_value(&value)).to_owned());
+            }
+            _ => {}
+        }
+    }
+    if !has_headers {
+        return;
+    }
+
+    let mut body = String::new();
+    while let Some(line) = lines.peek() {
+        if line.starts_with(b"diff --git ") || line.starts_with(b"--- ") || trim_eol(line) == b"---"
+        {
+            break;
+        }
+        body.push_str(&line.to_str_lossy());
+        lines.next();
+    }
+    let mut description = subject.unwrap_or_default();
+    let body = body.trim();
+    if !body.is_empty() {
+        description.push_str("\n\n");
+        description.push_str(body);
+    }
+    if !description.is_empty() {
+        description.push('\n');
+    }
+    patch.description = description;
+}
+
+fn parse_author(value: &str) -> Option<(String, String)> {
+    let value = value.trim_start();
+    let (name, rest) = if let Some(quoted) = value.strip_prefix('"') {
+        // RFC 5322 quoted string
+        let mut name = String::new();
+        let mut chars = quoted.char_indices();
+        let end = loop {
+            match chars.next()? {
+                (_, '\\') => name.push(chars.next()?.1),
+                (index, '"') => break index,
+                (_, c) => name.push(c),
+            }
+        };
+        let (_, rest) = quoted[end + 1..].split_once('<')?;
+        (name, rest)
+    } else {
+        let (name, rest) = value.split_once('<')?;
+        (name.trim().to_owned(), rest)
+    };
+    let (email, _) = rest.split_once('>')?;
+    Some((name, email.trim().to_owned()))
+}
+
+/// Formats the display name of a `From:` header, quoting it if it contains
+/// RFC 5322 special characters, or encoding it if it contains non-ASCII
+/// characters.
+pub fn encode_display_name(name: &str) -> Cow<'_, str> {
+    if !name.is_ascii() {
+        return encode_header_value(name);
+    }
+    if !name.contains(|c: char| "()<>[]:;@\\,.\"".contains(c)) {
+        return Cow::Borrowed(name);
+    }
+    let mut quoted = String::from('"');
+    for c in name.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    Cow::Owned(quoted)
+}
+
+/// Encodes the header value as RFC 2047 encoded words if it contains
+/// non-ASCII characters.
+///
+/// Long values are split into several encoded words on folded lines.
+pub fn encode_header_value(value: &str) -> Cow<'_, str> {
+    if value.is_ascii() {
+        return Cow::Borrowed(value);
+    }
+    // Encoded words must not be longer than 75 characters
+    const MAX_ENCODED_TEXT_LEN: usize = 75 - "=?UTF-8?q??=".len();
+    let mut words = vec![String::new()];
+    for c in value.chars() {
+        let mut encoded = String::new();
+        let mut buf = [0; 4];
+        for &b in c.encode_utf8(&mut buf).as_bytes() {
+            match b {
+                b' ' => encoded.push('_'),
+                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'!' | b'*' | b'+' | b'-' | b'/' => {
+                    encoded.push(char::from(b));
+                }
+                _ => encoded.push_str(&format!("={b:02X}")),
+            }
+        }
+        // Characters are never split across words
+        if words.last().unwrap().len() + encoded.len() > MAX_ENCODED_TEXT_LEN {
+            words.push(String::new());
+        }
+        words.last_mut().unwrap().push_str(&encoded);
+    }
+    Cow::Owned(
+        words
+            .iter()
+            .map(|word| format!("=?UTF-8?q?{word}?="))
+            .join("\n "),
+    )
+}
+
+/// Decodes RFC 2047 encoded words in the header value.
+fn decode_header_value(value: &str) -> String {
+    let mut decoded = vec![];
+    let mut rest = value;
+    let mut after_encoded_word = false;
+    while let Some(start) = rest.find("=?") {
+        let (before, word) = rest.split_at(start);
+        if let Some((text, len)) = decode_encoded_word(word) {
+            // Whitespace between adjacent encoded words is ignored
+            if !(after_encoded_word && before.trim().is_empty()) {
+                decoded.extend_from_slice(before.as_bytes());
+            }
+            decoded.extend(text);
+            rest = &word[len..];
+            after_encoded_word = true;
+        } else {
+            decoded.extend_from_slice(&rest.as_bytes()[..start + 2]);
+            rest = &word[2..];
+            after_encoded_word = false;
+        }
+    }
+    decoded.extend_from_slice(rest.as_bytes());
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Decodes "=?charset?encoding?text?=" at the start of `word`, returning the
+/// decoded bytes and the length of the encoded word.
+fn decode_encoded_word(word: &str) -> Option<(Vec<u8>, usize)> {
+    let (charset_and_language, rest) = word.strip_prefix("=?")?.split_once('?')?;
+    let (encoding, rest) = rest.split_once('?')?;
+    let (text, _) = rest.split_once("?=")?;
+    // Language suffix as in "UTF-8*en" is ignored
+    let charset = charset_and_language.split('*').next().unwrap();
+    if !(charset.eq_ignore_ascii_case("utf-8") || charset.eq_ignore_ascii_case("us-ascii"))
+        || text.contains(char::is_whitespace)
+    {
+        return None;
+    }
+    let decoded = match encoding {
+        "q" | "Q" => decode_q(text)?,
+        "b" | "B" => decode_base64(text)?,
+        _ => return None,
+    };
+    let len = "=??".len()
+        + charset_and_language.len()
+        + encoding.len()
+        + "?".len()
+        + text.len()
+        + "?=".len();
+    Some((decoded, len))
+}
+
+fn decode_q(text: &str) -> Option<Vec<u8>> {
+    let mut decoded = vec![];
+    let mut bytes = text.bytes();
+    while let Some(b) = bytes.next() {
+        match b {
+            b'_' => decoded.push(b' '),
+            b'=' => {
+                let hex = [bytes.next()?, bytes.next()?];
+                decoded.push(u8::from_str_radix(hex.to_str().ok()?, 16).ok()?);
+            }
+            _ => decoded.push(b),
+        }
+    }
+    Some(decoded)
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let mut decoded = vec![];
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for b in text.bytes().take_while(|&b| b != b'=') {
+        let value = match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a' + 26,
+            b'0'..=b'9' => b - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+        }
+    }
+    Some(decoded)
+}
+
+/// Strips `[PATCH ...]` prefix added by `git format-patch`.
+fn strip_patch_prefix(subject: &str) -> &str {
+    if subject.starts_with('[')
+        && let Some((prefix, rest)) = subject.split_once(']')
+        && prefix.contains("PATCH")
+    {
+        rest.trim_start()
+    } else {
+        subject
+    }
+}
+
+fn parse_extended_header(line: &[u8], file: &mut FilePatch) -> Result<(), PatchParseError> {
+    if let Some(mode) = line.strip_prefix(b"new file mode ") {
+        file.old_path = None;
+        file.new_mode = Some(FileMode::parse(mode)?);
+    } else if let Some(mode) = line.strip_prefix(b"deleted file mode ") {
+        file.new_path = None;
+        file.old_mode = Some(FileMode::parse(mode)?);
+    } else if let Some(mode) = line.strip_prefix(b"old mode ") {
+        file.old_mode = Some(FileMode::parse(mode)?);
+    } else if let Some(mode) = line.strip_prefix(b"new mode ") {
+        file.new_mode = Some(FileMode::parse(mode)?);
+    } else if let Some(path) = line.strip_prefix(b"rename from ") {
+        file.old_path = Some(parse_path(&unquote_path(path), 0)?);
+    } else if let Some(path) = line.strip_prefix(b"rename to ") {
+        file.new_path = Some(parse_path(&unquote_path(path), 0)?);
+    } else if let Some(path) = line.strip_prefix(b"copy from ") {
+        file.is_copy = true;
+        file.old_path = Some(parse_path(&unquote_path(path), 0)?);
+    } else if let Some(path) = line.strip_prefix(b"copy to ") {
+        file.is_copy = true;
+        file.new_path = Some(parse_path(&unquote_path(path), 0)?);
+    } else if let Some(rest) = line.strip_prefix(b"index ") {
+        // "index <old>..<new> <mode>"
+        if let Some((_, mode)) = rest.split_once_str(" ") {
+            let mode = FileMode::parse(mode)?;
+            file.old_mode.get_or_insert(mode);
+            file.new_mode.get_or_insert(mode);
+        }
+    } else if line.starts_with(b"Binary files ") || line == b"GIT binary patch" {
+        file.is_binary = true;
+    }
+    Ok(())
+}
+
+fn parse_hunk<'a>(
+    header: &[u8],
+    lines: &mut std::iter::Peekable<impl Iterator<Item = &'a [u8]>>,
+) -> Result<Hunk, PatchParseError> {
+    let invalid_header = || PatchParseError::InvalidHunkHeader {
+        line: trim_eol(header).into(),
+    };
+    let ranges = header
+        .strip_prefix(b"@@ ")
+        .and_then(|rest| rest.split_once_str(" @@"))
+        .map(|(ranges, _)| ranges)
+        .ok_or_else(invalid_header)?;
+    let (old_range, new_range) = ranges.split_once_str(" ").ok_or_else(invalid_header)?;
+    let (old_start, mut old_remaining) = old_range
+        .strip_prefix(b"-")
+        .and_then(parse_range)
+        .ok_or_else(invalid_header)?;
+    let (new_start, mut new_remaining) = new_range
+        .strip_prefix(b"+")
+        .and_then(parse_range)
+        .ok_or_else(invalid_header)?;
+
+    let mut hunk = Hunk {
+        old_start,
+        new_start,
+        lines: vec![],
+    };
+    while old_remaining > 0 || new_remaining > 0 {
+        let line = lines.next().ok_or(PatchParseError::TruncatedHunk)?;
+        // Some mail clients strip the trailing space of empty context lines.
+        let (marker, text) = match line.split_first() {
+            Some((marker, text)) if *marker != b'\n' && *marker != b'\r' => (*marker, text),
+            _ => (b' ', line),
+        };
+        match marker {
+            b' ' if old_remaining > 0 && new_remaining > 0 => {
+                old_remaining -= 1;
+                new_remaining -= 1;
+                hunk.lines.push(HunkLine::Context(text.into()));
+            }
+            b'-' if old_remaining > 0 => {
+                old_remaining -= 1;
+                hunk.lines.push(HunkLine::Removed(text.into()));
+            }
+            b'+' if new_remaining > 0 => {
+                new_remaining -= 1;
+                hunk.lines.push(HunkLine::Added(text.into()));
+            }
+            b'\\' => strip_last_line_terminator(&mut hunk),
+            _ => {
+                return Err(PatchParseError::InvalidHunkLine {
+                    line: trim_eol(line).into(),
+                });
+            }
+        }
+    }
+    if lines.peek().is_some_and(|line| line.starts_with(b"\\")) {
+        lines.next();
+        strip_last_line_terminator(&mut hunk);
+    }
+    Ok(hunk)
+}
+
+fn strip_last_line_terminator(hunk: &mut Hunk) {
+    if let Some(HunkLine::Context(text) | HunkLine::Removed(text) | HunkLine::Added(text)) =
+        hunk.lines.last_mut()
+    {
+        let len = trim_eol(text).len();
+        text.truncate(len);
+    }
+}
+
+/// Parses "start,count" or "start" hunk range.
+fn parse_range(range: &[u8]) -> Option<(usize, usize)> {
+    let (start, count) = match range.split_once_str(",") {
+        Some((start, count)) => (start, count.to_str().ok()?.parse().ok()?),
+        None => (range, 1),
+    };
+    Some((start.to_str().ok()?.parse().ok()?, count))
+}
+
+/// Splits "a/<old> b/<new>" of a `diff --git` line.
+fn split_git_diff_paths(paths: &[u8]) -> Option<(BString, BString)> {
+    if paths.starts_with(b"\"") {
+        let (old, rest) = split_quoted(paths)?;
+        let new = unquote_path(rest.trim_start());
+        return Some((old, new));
+    }
+    // The paths are the same unless the file is renamed or copied, in which
+    // case the actual paths are taken from the extended headers.
+    let len = paths.len().checked_sub(1)? / 2;
+    let (old, new) = (&paths[..len], &paths[len + 1..]);
+    if paths.get(len) == Some(&b' ') && old.get(2..) == new.get(2..) {
+        Some((old.into(), new.into()))
+    } else {
+        let (old, new) = paths.split_once_str(" b/")?;
+        Some((old.into(), [b"b/", new].concat().into()))
+    }
+}
+
+/// Parses path of "--- " or "+++ " line, or returns `None` for `/dev/null`.
+fn parse_file_header_path(value: &[u8]) -> Result<Option<RepoPathBuf>, PatchParseError> {
+    let value = trim_eol(value);
+    let path = if value.starts_with(b"\"") {
+        unquote_path(value)
+    } else {
+        // Strip timestamp added by diff(1)
+        value.split_str("\t").next().unwrap_or(value).into()
+    };
+    if path == "/dev/null" {
+        Ok(None)
+    } else {
+        let strip = usize::from(path.starts_with(b"a/") || path.starts_with(b"b/"));
+        parse_path(&path, strip).map(Some)
+    }
+}
+
+fn parse_path(path: &[u8], strip_components: usize) -> Result<RepoPathBuf, PatchParseError> {
+    let invalid = || PatchParseError::InvalidPath { path: path.into() };
+    let mut components = path.split_str("/");
+    for _ in 0..strip_components {
+        components.next();
+    }
+    let path = components.collect::<Vec<_>>().join(b"/".as_slice());
+    let path = path.to_str().map_err(|_| invalid())?;
+    if path.is_empty() {
+        return Err(invalid());
+    }
+    RepoPathBuf::from_internal_string(path).map_err(|_| invalid())
+}
+
+fn split_quoted(value: &[u8]) -> Option<(BString, &[u8])> {
+    let mut escaped = false;
+    for (i, &b) in value.iter().enumerate().skip(1) {
+        match b {
+            b'\\' if !escaped => escaped = true,
+            b'"' if !escaped => return Some((unquote_path(&value[..=i]), &value[i + 1..])),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+/// Decodes C-style quoted path as emitted by Git for unusual file names.
+fn unquote_path(value: &[u8]) -> BString {
+    let Some(quoted) = value
+        .strip_prefix(b"\"")
+        .and_then(|value| value.strip_suffix(b"\""))
+    else {
+        return value.into();
+    };
+    let mut path = BString::default();
+    let mut bytes = quoted.iter().copied();
+    while let Some(b) = bytes.next() {
+        if b != b'\\' {
+            path.push(b);
+            continue;
+        }
+        match bytes.next() {
+            Some(b'n') => path.push(b'\n'),
+            Some(b't') => path.push(b'\t'),
+            Some(digit @ b'0'..=b'7') => {
+                let mut value = u32::from(digit - b'0');
+                for _ in 0..2 {
+                    if let Some(digit) = bytes.next() {
+                        value = value * 8 + u32::from(digit.wrapping_sub(b'0'));
+                    }
+                }
+                path.push(value as u8);
+            }
+            Some(other) => path.push(other),
+            None => {}
+        }
+    }
+    path
+}
+
+fn trim_eol(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+/// Maximum number of lines a hunk may be moved from its recorded position.
+const MAX_HUNK_OFFSET: usize = 100;
+
+/// Applies hunks to the given content.
+///
+/// Each hunk is looked up within [`MAX_HUNK_OFFSET`] lines of its recorded
+/// position, shifted by the offset the previous hunk was applied at. Hunks
+/// without context or removed lines must apply exactly at their recorded
+/// position. Returns the index of the first hunk that doesn't apply on error.
+pub fn apply_hunks(content: &[u8], hunks: &[Hunk]) -> Result<BString, usize> {
+    let lines: Vec<&[u8]> = content.lines_with_terminator().collect();
+    let mut output = BString::default();
+    let mut pos = 0;
+    let mut shift: isize = 0;
+    for (index, hunk) in hunks.iter().enumerate() {
+        let old_lines: Vec<&[u8]> = hunk.old_lines().collect();
+        let matches_at = |start: usize| {
+            lines
+                .get(start..start + old_lines.len())
+                .is_some_and(|candidate| candidate == old_lines)
+        };
+        let start = if old_lines.is_empty() {
+            // An empty old range starts after the line it refers to, e.g.
+            // "-3,0" inserts after line 3. It would match anywhere, so it
+            // isn't moved.
+            Some(hunk.old_start).filter(|&start| start >= pos && start <= lines.len())
+        } else {
+            let recorded = hunk.old_start.saturating_sub(1);
+            let expected = recorded.saturating_add_signed(shift);
+            let start = (0..=MAX_HUNK_OFFSET)
+                .flat_map(|offset| [expected.checked_add(offset), expected.checked_sub(offset)])
+                .flatten()
+                .filter(|&start| start >= pos && start <= lines.len())
+                .find(|&start| matches_at(start));
+            if let Some(start) = start {
+                shift = start as isize - recorded as isize;
+            }
+            start
+        }
+        .ok_or(index + 1)?;
+        for line in &lines[pos..start] {
+            output.extend_from_slice(line);
+        }
+        for line in hunk.new_lines() {
+            output.extend_from_slice(line);
+        }
+        pos = start + old_lines.len();
+    }
+    for line in &lines[pos..] {
+        output.extend_from_slice(line);
+    }
+    Ok(output)
+}
+
+/// Applies the file patches to the given tree, returning the new tree.
+pub async fn apply_file_patches(
+    tree: &MergedTree,
+    files: &[FilePatch],
+) -> Result<MergedTree, PatchApplyError> {
+    let store = tree.store();
+    let mut builder = MergedTreeBuilder::new(tree.clone());
+    // Values written by earlier file patches, which must be seen by later
+    // ones touching the same paths.
+    let mut written: HashMap<RepoPathBuf, MergedTreeValue> = HashMap::new();
+    for file in files {
+        let Some(path) = file.path() else {
+            continue;
+        };
+        if file.is_binary {
+            return Err(PatchApplyError::BinaryPatch {
+                path: path.to_owned(),
+            });
+        }
+        let (old_content, old_mode) = match &file.old_path {
+            Some(old_path) => {
+                let value = current_path_value(tree, &written, old_path).await?;
+                let (content, mode) = match value.into_resolved() {
+                    Ok(Some(TreeValue::File { id, executable, .. })) => {
+                        let mut reader = store.read_file(old_path, &id).await?;
+                        let mut content = vec![];
+                        reader.read_to_end(&mut content).await.map_err(|err| {
+                            BackendError::ReadFile {
+                                path: old_path.clone(),
+                                id: id.clone(),
+                                source: err.into(),
+                            }
+                        })?;
+                        let mode = if executable {
+                            FileMode::Executable
+                        } else {
+                            FileMode::Normal
+                        };
+                        (content, mode)
+                    }
+                    Ok(Some(TreeValue::Symlink(id))) => {
+                        let target = store.read_symlink(old_path, &id).await?;
+                        (target.into_bytes(), FileMode::Symlink)
+                    }
+                    Ok(None) => {
+                        return Err(PatchApplyError::MissingFile {
+                            path: old_path.clone(),
+                        });
+                    }
+                    Ok(Some(_)) | Err(_) => {
+                        return Err(PatchApplyError::UnsupportedFile {
+                            path: old_path.clone(),
+                        });
+                    }
+                };
+                (content, Some(mode))
+            }
+            None => (vec![], None),
+        };
+        // New files, renames, and copies must not overwrite existing files.
+        if let Some(new_path) = &file.new_path
+            && file.old_path.as_ref() != Some(new_path)
+            && current_path_value(tree, &written, new_path)
+                .await?
+                .is_present()
+        {
+            return Err(PatchApplyError::FileExists {
+                path: new_path.clone(),
+            });
+        }
+
+        let new_content = apply_hunks(&old_content, &file.hunks).map_err(|index| {
+            PatchApplyError::HunkMismatch {
+                path: path.to_owned(),
+                index,
+            }
+        })?;
+        if let Some(old_path) = &file.old_path
+            && (file.new_path.as_ref() != Some(old_path) && !file.is_copy)
+        {
+            builder.set_or_remove(old_path.clone(), Merge::absent());
+            written.insert(old_path.clone(), Merge::absent());
+        }
+        let Some(new_path) = &file.new_path else {
+            continue;
+        };
+        let mode = file
+            .new_mode
+            .or(old_mode)
+            .or(file.old_mode)
+            .unwrap_or(FileMode::Normal);
+        let value = match mode {
+            FileMode::Normal | FileMode::Executable => {
+                let id = store
+                    .write_file(new_path, &mut new_content.as_slice())
+                    .await?;
+                TreeValue::File {
+                    id,
+                    executable: mode == FileMode::Executable,
+                    copy_id: CopyId::placeholder(),
+                }
+            }
+            FileMode::Symlink => {
+                let target = new_content
+                    .to_str()
+                    .map_err(|_| PatchApplyError::InvalidSymlink {
+                        path: new_path.clone(),
+                    })?;
+                TreeValue::Symlink(store.write_symlink(new_path, target).await?)
+            }
+        };
+        builder.set_or_remove(new_path.clone(), Merge::normal(value.clone()));
+        written.insert(new_path.clone(), Merge::normal(value));
+    }
+    Ok(builder.write_tree()?)
+}
+
+async fn current_path_value(
+    tree: &MergedTree,
+    written: &HashMap<RepoPathBuf, MergedTreeValue>,
+    path: &RepoPath,
+) -> BackendResult<MergedTreeValue> {
+    match written.get(path) {
+        Some(value) => Ok(value.clone()),
+        None => tree.path_value_async(path).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    fn repo_path(value: &str) -> RepoPathBuf {
+        RepoPathBuf::from_internal_string(value).unwrap()
+    }
+
+    fn context(text: &str) -> HunkLine {
+        HunkLine::Context(text.into())
+    }
+
+    fn removed(text: &str) -> HunkLine {
+        HunkLine::Removed(text.into())
+    }
+
+    fn added(text: &str) -> HunkLine {
+        HunkLine::Added(text.into())
+    }
+
+    #[test]
+    fn test_parse_plain_unified_diff() {
+        let patch = parse_patch(indoc! {b"
+            --- a/file\t2001-02-03 04:05:06
+            +++ b/file\t2001-02-03 04:05:07
+            @@ -1,3 +1,3 @@
+             a
+            -b
+            +B
+             c
+        "})
+        .unwrap();
+        assert_eq!(patch.description, "");
+        assert_eq!(
+            patch.files,
+            vec![FilePatch {
+                old_path: Some(repo_path("file")),
+                new_path: Some(repo_path("file")),
+                hunks: vec![Hunk {
+                    old_start: 1,
+                    new_start: 1,
+                    lines: vec![context("a\n"), removed("b\n"), added("B\n"), context("c\n")],
+                }],
+                ..Default::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_git_format_patch() {
+        let patch = parse_patch(indoc! {b"
+            From 0123456789abcdef0123456789abcdef01234567 Mon Sep 17 00:00:00 2001
+            From: Some One <some.one@example.com>
+            Date: Sat, 3 Feb 2001 04:05:06 +0700
+            Subject: [PATCH 1/2] Add new file and
+             rename another
+
+            Longer description.
+            ---
+             new | 1 +
+             1 file changed, 1 insertion(+)
+
+            diff --git a/new b/new
+            new file mode 100755
+            index 0000000..3b18e51
+            --- /dev/null
+            +++ b/new
+            @@ -0,0 +1 @@
+            +hello
+            \\ No newline at end of file
+            diff --git a/old name b/new name
+            similarity index 100%
+            rename from old name
+            rename to new name
+            diff --git a/gone b/gone
+            deleted file mode 100644
+            index 3b18e51..0000000
+            --- a/gone
+            +++ /dev/null
+            @@ -1 +0,0 @@
+            -bye
+            --
+            2.40.0
+        "})
+        .unwrap();
+        assert_eq!(
+            patch.author,
+            Some(("Some One".to_owned(), "some.one@example.com".to_owned()))
+        );
+        let datetime = patch.author_timestamp.unwrap().to_datetime().unwrap();
+        assert_eq!(datetime.to_rfc3339(), "2001-02-03T04:05:06+07:00");
+        assert_eq!(
+            patch.description,
+            "Add new file and rename another\n\nLonger description.\n"
+        );
+        assert_eq!(
+            patch.files,
+            vec![
+                FilePatch {
+                    old_path: None,
+                    new_path: Some(repo_path("new")),
+                    new_mode: Some(FileMode::Executable),
+                    hunks: vec![Hunk {
+                        old_start: 0,
+                        new_start: 1,
+                        lines: vec![added("hello")],
+                    }],
+                    ..Default::default()
+                },
+                FilePatch {
+                    old_path: Some(repo_path("old name")),
+                    new_path: Some(repo_path("new name")),
+                    ..Default::default()
+                },
+                FilePatch {
+                    old_path: Some(repo_path("gone")),
+                    new_path: None,
+                    old_mode: Some(FileMode::Normal),
+                    hunks: vec![Hunk {
+                        old_start: 1,
+                        new_start: 0,
+                        lines: vec![removed("bye\n")],
+                    }],
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_patch_series() {
+        let patches = parse_patch_series(indoc! {b"
+            From 0123456789abcdef0123456789abcdef01234567 Mon Sep 17 00:00:00 2001
+            From: Some One <some.one@example.com>
+            Subject: [PATCH 1/2] First
+
+            From the description.
+            From now on: not a separator
+            Signed-off-by: Some One <some.one@example.com>
+            ---
+            diff --git a/file b/file
+            --- a/file
+            +++ b/file
+            @@ -1 +1 @@
+            -a
+            +b
+            --
+            2.40.0
+
+            From 89abcdef0123456789abcdef0123456789abcdef Mon Sep 17 00:00:00 2001
+            From: Some One <some.one@example.com>
+            Subject: [PATCH 2/2] Second
+
+            ---
+            diff --git a/file b/file
+            --- a/file
+            +++ b/file
+            @@ -1 +1 @@
+            -b
+            +c
+            --
+            2.40.0
+        "})
+        .unwrap();
+        assert_eq!(patches.len(), 2);
+        assert_eq!(
+            patches[0].description,
+            indoc! {"
+                First
+
+                From the description.
+                From now on: not a separator
+                Signed-off-by: Some One <some.one@example.com>
+            "}
+        );
+        assert_eq!(
+            patches[0].files[0].hunks[0].lines,
+            [removed("a\n"), added("b\n")]
+        );
+        assert_eq!(patches[1].description, "Second\n");
+        assert_eq!(
+            patches[1].files[0].hunks[0].lines,
+            [removed("b\n"), added("c\n")]
+        );
+
+        // Plain diff is a single patch
+        let patches = parse_patch_series(b"--- a/f\n+++ b/f\n@@ -1 +1 @@\n-a\n+b\n").unwrap();
+        assert_eq!(patches.len(), 1);
+    }
+
+    #[test]
+    fn test_display_name_encoding() {
+        assert_eq!(encode_display_name("John Doe"), "John Doe");
+        assert_eq!(encode_display_name("Doe, John"), r#""Doe, John""#);
+        assert_eq!(
+            encode_display_name(r#"J. "Jo\ Doe"#),
+            r#""J. \"Jo\\ Doe""#
+        );
+        assert_eq!(
+            encode_display_name("Jöhn Dœ"),
+            "=?UTF-8?q?J=C3=B6hn_D=C5=93?="
+        );
+
+        for name in ["John Doe", "Doe, John", r#"J. "Jo\ Doe"#, "<x> (y)"] {
+            let header = format!("{} <j@example.com>", encode_display_name(name));
+            assert_eq!(
+                parse_author(&header),
+                Some((name.to_owned(), "j@example.com".to_owned()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_header_encoding() {
+        assert_eq!(encode_header_value("plain text"), "plain text");
+        assert_eq!(
+            encode_header_value("Jöhn Dœ"),
+            "=?UTF-8?q?J=C3=B6hn_D=C5=93?="
+        );
+        // Long values are folded without splitting characters
+        let long = "ö".repeat(40);
+        let encoded = encode_header_value(&long);
+        assert!(encoded.lines().all(|line| line.trim().len() <= 75));
+        assert_eq!(encoded.lines().count(), 4);
+
+        for value in ["plain text", "Jöhn Dœ", &long, "a_b=c?d"] {
+            let folded = encode_header_value(value).replace("\n", "");
+            assert_eq!(decode_header_value(&folded), value);
+        }
+        assert_eq!(
+            decode_header_value("[PATCH] =?utf-8?B?SsO2aG4=?= =?UTF-8?Q?_D=C5=93?= x"),
+            "[PATCH] Jöhn Dœ x"
+        );
+        // Invalid encoded words are kept as is
+        assert_eq!(decode_header_value("=?UTF-8?x?abc?="), "=?UTF-8?x?abc?=");
+        assert_eq!(decode_header_value("a =? b"), "a =? b");
+
+        let patch = parse_patch(indoc! {b"
+            From: =?UTF-8?q?J=C3=B6hn?= <j@example.com>
+            Subject: [PATCH] =?UTF-8?q?H=C3=A9llo?=
+             =?UTF-8?q?_w=C3=B6rld?=
+
+        "})
+        .unwrap();
+        assert_eq!(
+            patch.author,
+            Some(("Jöhn".to_owned(), "j@example.com".to_owned()))
+        );
+        assert_eq!(patch.description, "Héllo wörld\n");
+    }
+
+    #[test]
+    fn test_parse_quoted_path() {
+        let patch = parse_patch(indoc! {br#"
+            diff --git "a/tab\there" "b/tab\there"
+            old mode 100644
+            new mode 100755
+        "#})
+        .unwrap();
+        assert_eq!(patch.files[0].old_path, Some(repo_path("tab\there")));
+        assert_eq!(patch.files[0].new_path, Some(repo_path("tab\there")));
+        assert_eq!(patch.files[0].new_mode, Some(FileMode::Executable));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_matches::assert_matches!(
+            parse_patch(b"@@ -1 +1 @@\n-a\n+b\n"),
+            Err(PatchParseError::HunkWithoutFile { .. })
+        );
+        assert_matches::assert_matches!(
+            parse_patch(b"--- a/f\n+++ b/f\n@@ -1 +1,2 @@\n-a\n+b\n"),
+            Err(PatchParseError::TruncatedHunk)
+        );
+        assert_matches::assert_matches!(
+            parse_patch(b"--- a/f\n+++ b/f\n@@ -1,x +1 @@\n"),
+            Err(PatchParseError::InvalidHunkHeader { .. })
+        );
+        assert_matches::assert_matches!(
+            parse_patch(b"--- /dev/null\n+++ /dev/null\n@@ -0,0 +1 @@\n+a\n"),
+            Err(PatchParseError::MissingPath)
+        );
+        assert_matches::assert_matches!(
+            parse_patch(b"diff --git foo barbaz\nnew file mode 100644\n"),
+            Err(PatchParseError::MissingPath)
+        );
+        assert_matches::assert_matches!(
+            parse_patch(b"diff --git x y\nnew file mode 100644\n"),
+            Err(PatchParseError::InvalidPath { .. })
+        );
+    }
+
+    #[test]
+    fn test_apply_hunks() {
+        let hunks = parse_patch(indoc! {b"
+            --- a/f
+            +++ b/f
+            @@ -2,2 +2,2 @@
+             b
+            -c
+            +C
+            @@ -6 +6,2 @@
+             f
+            +g
+        "})
+        .unwrap()
+        .files
+        .remove(0)
+        .hunks;
+        assert_eq!(
+            apply_hunks(b"a\nb\nc\nd\ne\nf\n", &hunks).unwrap(),
+            "a\nb\nC\nd\ne\nf\ng\n"
+        );
+        // Hunks are found at an offset
+        assert_eq!(
+            apply_hunks(b"x\na\nb\nc\nd\ne\nf\n", &hunks).unwrap(),
+            "x\na\nb\nC\nd\ne\nf\ng\n"
+        );
+        assert_eq!(apply_hunks(b"a\nb\nx\n", &hunks), Err(1));
+        assert_eq!(apply_hunks(b"a\nb\nc\n", &hunks), Err(2));
+    }
+
+    #[test]
+    fn test_apply_hunks_without_context() {
+        let hunks = parse_patch(indoc! {b"
+            --- a/f
+            +++ b/f
+            @@ -0,0 +1 @@
+            +x
+            @@ -3,0 +5 @@
+            +y
+            @@ -4 +5,0 @@
+            -d
+        "})
+        .unwrap()
+        .files
+        .remove(0)
+        .hunks;
+        assert_eq!(
+            apply_hunks(b"a\nb\nc\nd\n", &hunks).unwrap(),
+            "x\na\nb\nc\ny\n"
+        );
+        // Insertions aren't moved, and can't be past the end of the file
+        let hunks = parse_patch(indoc! {b"
+            --- a/f
+            +++ b/f
+            @@ -5,0 +6 @@
+            +x
+        "})
+        .unwrap()
+        .files
+        .remove(0)
+        .hunks;
+        assert_eq!(
+            apply_hunks(b"a\nb\nc\nd\ne\n", &hunks).unwrap(),
+            "a\nb\nc\nd\ne\nx\n"
+        );
+        assert_eq!(apply_hunks(b"a\nb\nc\nd\n", &hunks), Err(1));
+    }
+
+    #[test]
+    fn test_apply_hunks_offset_limit() {
+        let hunks = parse_patch(indoc! {b"
+            --- a/f
+            +++ b/f
+            @@ -1 +1 @@
+            -a
+            +A
+            @@ -3 +3 @@
+            -c
+            +C
+        "})
+        .unwrap()
+        .files
+        .remove(0)
+        .hunks;
+        let padding = "x\n".repeat(MAX_HUNK_OFFSET);
+        // The second hunk is looked up relative to where the first one applied
+        assert_eq!(
+            apply_hunks(format!("{padding}a\nb\nc\n").as_bytes(), &hunks).unwrap(),
+            format!("{padding}A\nb\nC\n")
+        );
+        assert_eq!(
+            apply_hunks(format!("x\n{padding}a\nb\nc\n").as_bytes(), &hunks),
+            Err(1)
+        );
+        assert_eq!(
+            apply_hunks(format!("a\nb\nx\n{padding}c\n").as_bytes(), &hunks),
+            Err(2)
+        );
+    }
+}
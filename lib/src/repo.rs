@@ -106,6 +106,7 @@ use crate::simple_backend::SimpleBackend;
 use crate::simple_op_heads_store::SimpleOpHeadsStore;
 use crate::simple_op_store::SimpleOpStore;
 use crate::store::Store;
+use crate::store::StoreCacheOptions;
 use crate::submodule_store::SubmoduleStore;
 use crate::transaction::Transaction;
 use crate::transaction::TransactionCommitError;
@@ -218,7 +219,9 @@ impl ReadonlyRepo {
         fs::write(&backend_path, backend.name()).context(&backend_path)?;
         let merge_options =
             MergeOptions::from_settings(settings).map_err(|err| BackendInitError(err.into()))?;
-        let store = Store::new(backend, signer, merge_options);
+        let cache_options = StoreCacheOptions::from_settings(settings)
+            .map_err(|err| BackendInitError(err.into()))?;
+        let store = Store::new(backend, signer, merge_options, cache_options);
 
         let op_store_path = repo_path.join("op_store");
         fs::create_dir(&op_store_path).context(&op_store_path)?;
@@ -699,10 +702,13 @@ impl RepoLoader {
     ) -> Result<Self, StoreLoadError> {
         let merge_options =
             MergeOptions::from_settings(settings).map_err(|err| BackendLoadError(err.into()))?;
+        let cache_options = StoreCacheOptions::from_settings(settings)
+            .map_err(|err| BackendLoadError(err.into()))?;
         let store = Store::new(
             store_factories.load_backend(settings, &repo_path.join("store"))?,
             Signer::from_settings(settings)?,
             merge_options,
+            cache_options,
         );
         let root_op_data = RootOperationData {
             root_commit_id: store.root_commit_id().clone(),
@@ -66,6 +66,12 @@ pub struct RemoteSettings {
     /// String matcher expression whether to track bookmarks automatically.
     #[serde(default)]
     pub auto_track_bookmarks: Option<String>,
+    /// String matcher expression of bookmarks to fetch by default.
+    #[serde(default)]
+    pub fetch_bookmarks: Option<String>,
+    /// String matcher expression of bookmarks to push by default.
+    #[serde(default)]
+    pub push_bookmarks: Option<String>,
 }
 
 impl RemoteSettings {
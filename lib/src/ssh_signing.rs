@@ -28,6 +28,7 @@ use thiserror::Error;
 
 use crate::config::ConfigGetError;
 use crate::config::ConfigGetResultExt as _;
+use crate::file_util::expand_home_path;
 use crate::settings::UserSettings;
 use crate::signing::SigStatus;
 use crate::signing::SignError;
@@ -89,7 +90,7 @@ fn run_command(command: &mut Command, stdin: &[u8]) -> SshResult<Vec<u8>> {
 // If the given data is actually already a filepath to a key on disk then the
 // key input is returned directly.
 fn ensure_key_as_file(key: &str) -> SshResult<Either<PathBuf, tempfile::TempPath>> {
-    let key_path = crate::file_util::expand_home_path(key);
+    let key_path = expand_home_path(key);
     if key_path.is_absolute() {
         return Ok(either::Left(key_path));
     }
@@ -140,14 +141,14 @@ impl SshBackend {
             Ok(settings
                 .get_string(name)
                 .optional()?
-                .map(|v| crate::file_util::expand_home_path(v.as_str())))
+                .map(|v| expand_home_path(v.as_str())))
         };
 
         let allowed_signers = get_expanded_path("signing.backends.ssh.allowed-signers")?;
         let revocation_list = get_expanded_path("signing.backends.ssh.revocation-list")?;
 
         Ok(Self::new(
-            program.into(),
+            expand_home_path(&program).into(),
             allowed_signers.map(Into::into),
             revocation_list.map(Into::into),
         ))
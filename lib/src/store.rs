@@ -16,6 +16,8 @@
 
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::iter;
+use std::num::NonZeroUsize;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -23,11 +25,13 @@ use std::time::SystemTime;
 
 use clru::CLruCache;
 use futures::stream::BoxStream;
+use itertools::Itertools as _;
 use pollster::FutureExt as _;
 use tokio::io::AsyncRead;
 
 use crate::backend;
 use crate::backend::Backend;
+use crate::backend::BackendError;
 use crate::backend::BackendResult;
 use crate::backend::ChangeId;
 use crate::backend::CommitId;
@@ -37,19 +41,37 @@ use crate::backend::SigningFn;
 use crate::backend::SymlinkId;
 use crate::backend::TreeId;
 use crate::commit::Commit;
+use crate::config::ConfigGetError;
 use crate::index::Index;
 use crate::merge::Merge;
 use crate::merged_tree::MergedTree;
 use crate::repo_path::RepoPath;
 use crate::repo_path::RepoPathBuf;
+use crate::settings::UserSettings;
 use crate::signing::Signer;
 use crate::tree::Tree;
 use crate::tree_merge::MergeOptions;
 
-// There are more tree objects than commits, and trees are often shared across
-// commits.
 pub(crate) const COMMIT_CACHE_CAPACITY: usize = 100;
-const TREE_CACHE_CAPACITY: usize = 1000;
+
+/// Capacities of the in-memory object caches of the [`Store`].
+#[derive(Clone, Debug)]
+pub struct StoreCacheOptions {
+    /// Maximum number of commit objects to cache.
+    pub commit_capacity: NonZeroUsize,
+    /// Maximum number of tree objects to cache.
+    pub tree_capacity: NonZeroUsize,
+}
+
+impl StoreCacheOptions {
+    /// Loads cache options from `settings`.
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        Ok(Self {
+            commit_capacity: settings.get("store.commit-cache-size")?,
+            tree_capacity: settings.get("store.tree-cache-size")?,
+        })
+    }
+}
 
 /// Wraps the low-level backend and makes it return more convenient types. Also
 /// adds caching.
@@ -74,12 +96,13 @@ impl Store {
         backend: Box<dyn Backend>,
         signer: Signer,
         merge_options: MergeOptions,
+        cache_options: StoreCacheOptions,
     ) -> Arc<Self> {
         Arc::new(Self {
             backend,
             signer,
-            commit_cache: Mutex::new(CLruCache::new(COMMIT_CACHE_CAPACITY.try_into().unwrap())),
-            tree_cache: Mutex::new(CLruCache::new(TREE_CACHE_CAPACITY.try_into().unwrap())),
+            commit_cache: Mutex::new(CLruCache::new(cache_options.commit_capacity)),
+            tree_cache: Mutex::new(CLruCache::new(cache_options.tree_capacity)),
             merge_options,
         })
     }
@@ -157,6 +180,49 @@ impl Store {
         Ok(Commit::new(self.clone(), id.clone(), data))
     }
 
+    /// Reads multiple commits, returned in the same order as `ids`. Commits
+    /// that aren't cached are read from the backend in one batch.
+    pub async fn get_commits_async(
+        self: &Arc<Self>,
+        ids: &[CommitId],
+    ) -> BackendResult<Vec<Commit>> {
+        let mut data_list = {
+            let mut locked_cache = self.commit_cache.lock().unwrap();
+            ids.iter()
+                .map(|id| locked_cache.get(id).cloned())
+                .collect_vec()
+        };
+        let missing_ids = iter::zip(ids, &data_list)
+            .filter(|(_, data)| data.is_none())
+            .map(|(id, _)| id.clone())
+            .collect_vec();
+        if !missing_ids.is_empty() {
+            let commits = self.backend.read_commits(&missing_ids).await?;
+            if commits.len() != missing_ids.len() {
+                return Err(BackendError::Other(
+                    format!(
+                        "Backend returned {} commits for {} requested commits",
+                        commits.len(),
+                        missing_ids.len()
+                    )
+                    .into(),
+                ));
+            }
+            let mut locked_cache = self.commit_cache.lock().unwrap();
+            let mut missing_data = iter::zip(missing_ids, commits).map(|(id, commit)| {
+                let data = Arc::new(commit);
+                locked_cache.put(id, data.clone());
+                data
+            });
+            for data in data_list.iter_mut().filter(|data| data.is_none()) {
+                *data = missing_data.next();
+            }
+        }
+        Ok(iter::zip(ids, data_list)
+            .map(|(id, data)| Commit::new(self.clone(), id.clone(), data.unwrap()))
+            .collect())
+    }
+
     async fn get_backend_commit(&self, id: &CommitId) -> BackendResult<Arc<backend::Commit>> {
         {
             let mut locked_cache = self.commit_cache.lock().unwrap();
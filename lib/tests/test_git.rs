@@ -3792,6 +3792,124 @@ fn test_fetch_multiple_branches() {
     );
 }
 
+#[test]
+fn test_fetch_concurrently() {
+    let temp_dir = testutils::new_temp_dir();
+    let origin_repo = testutils::git::init_bare(temp_dir.path().join("origin"));
+    let upstream_repo = testutils::git::init_bare(temp_dir.path().join("upstream"));
+    let origin_commit = empty_git_commit(&origin_repo, "refs/heads/main", &[]);
+    let upstream_commit = empty_git_commit(&upstream_repo, "refs/heads/feature", &[]);
+
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let git_repo = get_git_repo(&test_repo.repo);
+    for (name, remote_repo) in [("origin", &origin_repo), ("upstream", &upstream_repo)] {
+        testutils::git::add_remote(
+            git_repo.path(),
+            name,
+            &remote_repo.path().display().to_string(),
+        );
+    }
+    // Reload after Git configuration change.
+    let repo = &test_repo
+        .env
+        .load_repo_at_head(&testutils::user_settings(), test_repo.repo_path());
+
+    let subprocess_options = GitSubprocessOptions::from_settings(repo.settings()).unwrap();
+    let import_options = default_import_options();
+    let mut tx = repo.start_transaction();
+    let mut fetcher =
+        GitFetch::new(tx.repo_mut(), subprocess_options.clone(), &import_options).unwrap();
+    let expand = |name: &'static str| {
+        let remote: &RemoteName = name.as_ref();
+        (
+            remote,
+            expand_fetch_refspecs(remote, StringExpression::all()).unwrap(),
+        )
+    };
+
+    // A missing remote is reported before anything is fetched
+    let result = fetcher.fetch_concurrently(
+        vec![expand("origin"), expand("invalid-remote")],
+        git::RemoteCallbacks::default(),
+        None,
+        None,
+    );
+    assert!(matches!(result, Err(GitFetchError::NoSuchRemote(_))));
+
+    fetcher
+        .fetch_concurrently(
+            vec![expand("origin"), expand("upstream")],
+            git::RemoteCallbacks::default(),
+            None,
+            None,
+        )
+        .unwrap();
+    let stats = fetcher.import_refs().unwrap();
+    assert_eq!(
+        stats
+            .changed_remote_bookmarks
+            .iter()
+            .map(|(symbol, _)| symbol)
+            .sorted()
+            .collect_vec(),
+        [
+            remote_symbol("feature", "upstream"),
+            remote_symbol("main", "origin"),
+        ]
+    );
+    let view = tx.repo().view();
+    assert_eq!(
+        view.get_remote_bookmark(remote_symbol("main", "origin"))
+            .target,
+        RefTarget::normal(jj_id(origin_commit))
+    );
+    assert_eq!(
+        view.get_remote_bookmark(remote_symbol("feature", "upstream"))
+            .target,
+        RefTarget::normal(jj_id(upstream_commit))
+    );
+
+    // Branches deleted from both remotes are pruned
+    for (remote_repo, name) in [
+        (&origin_repo, "refs/heads/main"),
+        (&upstream_repo, "refs/heads/feature"),
+    ] {
+        remote_repo.find_reference(name).unwrap().delete().unwrap();
+    }
+    let expand_exact = |remote: &'static str, name: &'static str| {
+        let remote: &RemoteName = remote.as_ref();
+        (
+            remote,
+            expand_fetch_refspecs(remote, StringExpression::exact(name)).unwrap(),
+        )
+    };
+    let mut fetcher = GitFetch::new(tx.repo_mut(), subprocess_options, &import_options).unwrap();
+    fetcher
+        .fetch_concurrently(
+            vec![
+                expand_exact("origin", "main"),
+                expand_exact("upstream", "feature"),
+            ],
+            git::RemoteCallbacks::default(),
+            None,
+            None,
+        )
+        .unwrap();
+    let stats = fetcher.import_refs().unwrap();
+    assert_eq!(
+        stats
+            .changed_remote_bookmarks
+            .iter()
+            .map(|(symbol, (_, new_target))| (symbol.as_ref(), new_target.is_absent()))
+            .sorted()
+            .collect_vec(),
+        [
+            (remote_symbol("feature", "upstream"), true),
+            (remote_symbol("main", "origin"), true),
+        ]
+    );
+}
+
 #[test]
 fn test_fetch_with_tag_changes() {
     let test_data = GitRepoData::create();
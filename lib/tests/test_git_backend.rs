@@ -24,6 +24,8 @@ use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
 use jj_lib::backend::CopyRecord;
 use jj_lib::commit::Commit;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigSource;
 use jj_lib::conflict_labels::ConflictLabels;
 use jj_lib::git_backend::GitBackend;
 use jj_lib::git_backend::JJ_TREES_COMMIT_HEADER;
@@ -34,6 +36,7 @@ use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo as _;
 use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::settings::UserSettings;
 use jj_lib::stacked_table::TableSegment as _;
 use jj_lib::stacked_table::TableStore;
 use jj_lib::store::Store;
@@ -367,6 +370,144 @@ fn test_copy_detection() {
     );
 }
 
+#[test]
+fn test_copy_detection_similarity() {
+    let mut config = testutils::base_user_config();
+    config.add_layer(ConfigLayer::parse(ConfigSource::User, "git.rename-similarity = 80").unwrap());
+    let settings = UserSettings::from_config(config).unwrap();
+    let test_repo = TestRepo::init_with_backend_and_settings(TestRepoBackend::Git, &settings);
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit_a = make_commit(
+        &mut tx,
+        vec![repo.store().root_commit_id().clone()],
+        &[
+            (repo_path("same"), "1\n2\n3\n4\n5\n"),
+            (repo_path("similar"), "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n"),
+            (repo_path("dissimilar"), "a\nb\nc\nd\ne\n"),
+        ],
+    );
+    let commit_b = make_commit(
+        &mut tx,
+        vec![commit_a.id().clone()],
+        &[
+            (repo_path("same2"), "1\n2\n3\n4\n5\n"),
+            (repo_path("similar2"), "1\n2\n3\n4\n5\n6\n7\n8\n9\nx\n"),
+            (repo_path("dissimilar2"), "a\nb\nc\ny\nz\n"),
+        ],
+    );
+
+    assert_eq!(
+        get_copy_records(repo.store(), None, &commit_a, &commit_b),
+        hashmap! {
+            "same2".to_owned() => "same".to_owned(),
+            "similar2".to_owned() => "similar".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_copy_detection_rename_limit() {
+    let new_repo = |limit: usize| {
+        let mut config = testutils::base_user_config();
+        config.add_layer(
+            ConfigLayer::parse(ConfigSource::User, &format!("git.rename-limit = {limit}")).unwrap(),
+        );
+        let settings = UserSettings::from_config(config).unwrap();
+        TestRepo::init_with_backend_and_settings(TestRepoBackend::Git, &settings)
+    };
+    let get_records = |test_repo: &TestRepo| {
+        let repo = &test_repo.repo;
+        let mut tx = repo.start_transaction();
+        let commit_a = make_commit(
+            &mut tx,
+            vec![repo.store().root_commit_id().clone()],
+            &[
+                (repo_path("a"), "1\n2\n3\n4\n5\n"),
+                (repo_path("b"), "a\nb\nc\nd\ne\n"),
+            ],
+        );
+        let commit_b = make_commit(
+            &mut tx,
+            vec![commit_a.id().clone()],
+            &[
+                (repo_path("a2"), "1\n2\n3\n4\nx\n"),
+                (repo_path("b2"), "a\nb\nc\nd\nx\n"),
+            ],
+        );
+        get_copy_records(repo.store(), None, &commit_a, &commit_b)
+    };
+
+    // 2 sources * 2 destinations are too many to compare
+    assert_eq!(get_records(&new_repo(3)), HashMap::default());
+    assert_eq!(
+        get_records(&new_repo(4)),
+        hashmap! {
+            "a2".to_owned() => "a".to_owned(),
+            "b2".to_owned() => "b".to_owned(),
+        }
+    );
+    // 0 means unlimited
+    assert_eq!(
+        get_records(&new_repo(0)),
+        hashmap! {
+            "a2".to_owned() => "a".to_owned(),
+            "b2".to_owned() => "b".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_copy_detection_cached() {
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit_a = make_commit(
+        &mut tx,
+        vec![repo.store().root_commit_id().clone()],
+        &[(repo_path("a"), "content1"), (repo_path("b"), "content2")],
+    );
+    let commit_b = make_commit(
+        &mut tx,
+        vec![commit_a.id().clone()],
+        &[(repo_path("a2"), "content1"), (repo_path("b2"), "content2")],
+    );
+
+    // The first query populates the cache with all records
+    let store = repo.store();
+    let all_records = hashmap! {
+        "a2".to_owned() => "a".to_owned(),
+        "b2".to_owned() => "b".to_owned(),
+    };
+    assert_eq!(
+        get_copy_records(store, None, &commit_a, &commit_b),
+        all_records
+    );
+    assert_eq!(
+        get_copy_records(store, None, &commit_a, &commit_b),
+        all_records
+    );
+    // Paths are filtered out of the cached records
+    assert_eq!(
+        get_copy_records(store, Some(&[repo_path_buf("b2")]), &commit_a, &commit_b),
+        hashmap! { "b2".to_owned() => "b".to_owned() }
+    );
+    assert_eq!(
+        get_copy_records(store, Some(&[repo_path_buf("a")]), &commit_a, &commit_b),
+        HashMap::default()
+    );
+    // The reverse direction isn't served from the cache
+    assert_eq!(
+        get_copy_records(store, None, &commit_b, &commit_a),
+        hashmap! {
+            "a".to_owned() => "a2".to_owned(),
+            "b".to_owned() => "b2".to_owned(),
+        }
+    );
+}
+
 #[test]
 fn test_copy_detection_file_and_dir() {
     let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
@@ -0,0 +1,104 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use assert_matches::assert_matches;
+use itertools::Itertools as _;
+use jj_lib::config::ConfigGetError;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigSource;
+use jj_lib::repo::Repo as _;
+use jj_lib::settings::UserSettings;
+use jj_lib::store::StoreCacheOptions;
+use pollster::FutureExt as _;
+use testutils::TestRepo;
+use testutils::test_backend::TestBackend;
+use testutils::write_random_commit;
+
+#[test]
+fn test_get_commits() {
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit_a = write_random_commit(tx.repo_mut());
+    let commit_b = write_random_commit(tx.repo_mut());
+    let commit_c = write_random_commit(tx.repo_mut());
+    tx.commit("test").unwrap();
+
+    // Start with an empty cache
+    let repo = test_repo
+        .env
+        .load_repo_at_head(&settings, test_repo.repo_path());
+    let store = repo.store();
+    store.get_commit(commit_b.id()).unwrap();
+
+    // Commits are returned in the requested order, whether they were cached
+    // or not
+    let ids = [commit_c.id(), commit_b.id(), commit_a.id(), commit_c.id()].map(|id| id.clone());
+    let commits = store.get_commits_async(&ids).block_on().unwrap();
+    assert_eq!(
+        commits.iter().map(|commit| commit.id()).collect_vec(),
+        ids.iter().collect_vec()
+    );
+    assert_eq!(commits[1], commit_b);
+    assert_eq!(commits[2], commit_a);
+
+    // Cached commits are not read from the backend again
+    let test_backend: &TestBackend = store.backend_impl().unwrap();
+    test_backend.remove_commit_unchecked(commit_a.id());
+    let commits = store
+        .get_commits_async(&[commit_a.id().clone()])
+        .block_on()
+        .unwrap();
+    assert_eq!(commits, std::slice::from_ref(&commit_a));
+
+    // Missing commits that aren't cached fail the whole batch
+    let repo = test_repo
+        .env
+        .load_repo_at_head(&settings, test_repo.repo_path());
+    assert!(
+        repo.store()
+            .get_commits_async(&[commit_b.id().clone(), commit_a.id().clone()])
+            .block_on()
+            .is_err()
+    );
+}
+
+#[test]
+fn test_cache_options_from_settings() {
+    let settings_with = |text: &str| {
+        let mut config = testutils::base_user_config();
+        config.add_layer(ConfigLayer::parse(ConfigSource::User, text).unwrap());
+        UserSettings::from_config(config).unwrap()
+    };
+
+    let options = StoreCacheOptions::from_settings(&testutils::user_settings()).unwrap();
+    assert_eq!(options.commit_capacity.get(), 100);
+    assert_eq!(options.tree_capacity.get(), 1000);
+
+    let settings = settings_with("store.commit-cache-size = 5\nstore.tree-cache-size = 7");
+    let options = StoreCacheOptions::from_settings(&settings).unwrap();
+    assert_eq!(options.commit_capacity.get(), 5);
+    assert_eq!(options.tree_capacity.get(), 7);
+
+    // Caches can't be disabled
+    for name in ["store.commit-cache-size", "store.tree-cache-size"] {
+        let settings = settings_with(&format!("{name} = 0"));
+        assert_matches!(
+            StoreCacheOptions::from_settings(&settings),
+            Err(ConfigGetError::Type { name: err_name, .. }) if err_name == name
+        );
+    }
+}